@@ -0,0 +1,242 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Call parameters passed by host as an archived rkyv buffer, they could be accessed
+//! without deserialization and so without allocations for AIR scripts and tetraplets.
+
+use crate::ArchivedCallParameters;
+use crate::CallParameters;
+
+use rkyv::AlignedVec;
+
+use std::fmt;
+
+/// A validated rkyv buffer with archived call parameters.
+pub struct ArchivedCallParametersBuffer {
+    bytes: ArchivedBytes,
+}
+
+enum ArchivedBytes {
+    /// Bytes passed by host in a buffer of u64, which is aligned enough for the archived type.
+    #[cfg_attr(
+        not(all(target_arch = "wasm32", feature = "host-api-v4")),
+        allow(dead_code)
+    )]
+    Words { words: Vec<u64>, len: usize },
+    /// Bytes passed to `from_bytes` if they are properly aligned.
+    Owned(Vec<u8>),
+    /// A copy of misaligned bytes passed to `from_bytes`.
+    Aligned(AlignedVec),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchivedCallParametersError(String);
+
+impl ArchivedCallParametersBuffer {
+    /// Validates archived call parameters, bytes are copied only if they aren't aligned enough
+    /// for the archived type.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, ArchivedCallParametersError> {
+        let alignment = std::mem::align_of::<ArchivedCallParameters>();
+        Self::validate(ArchivedBytes::new(bytes, alignment))
+    }
+
+    /// Validates archived call parameters stored in the first `len` bytes of `words`
+    /// without copying them.
+    #[cfg_attr(
+        not(all(target_arch = "wasm32", feature = "host-api-v4")),
+        allow(dead_code)
+    )]
+    fn from_words(words: Vec<u64>, len: usize) -> Result<Self, ArchivedCallParametersError> {
+        if len > words.len() * std::mem::size_of::<u64>() {
+            return Err(ArchivedCallParametersError(format!(
+                "archive size {} exceeds its buffer of {} words",
+                len,
+                words.len()
+            )));
+        }
+
+        Self::validate(ArchivedBytes::Words { words, len })
+    }
+
+    fn validate(bytes: ArchivedBytes) -> Result<Self, ArchivedCallParametersError> {
+        rkyv::check_archived_root::<CallParameters>(bytes.as_slice())
+            .map_err(|e| ArchivedCallParametersError(e.to_string()))?;
+
+        Ok(Self { bytes })
+    }
+
+    /// Returns archived call parameters.
+    pub fn get(&self) -> &ArchivedCallParameters {
+        // it's safe because bytes were validated while creating the buffer
+        unsafe { rkyv::archived_root::<CallParameters>(self.bytes.as_slice()) }
+    }
+}
+
+impl ArchivedBytes {
+    fn new(bytes: Vec<u8>, alignment: usize) -> Self {
+        if bytes.as_ptr() as usize % alignment == 0 {
+            return ArchivedBytes::Owned(bytes);
+        }
+
+        let mut aligned_bytes = AlignedVec::with_capacity(bytes.len());
+        aligned_bytes.extend_from_slice(&bytes);
+        ArchivedBytes::Aligned(aligned_bytes)
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            // it's safe because len doesn't exceed the size of words
+            ArchivedBytes::Words { words, len } => unsafe {
+                std::slice::from_raw_parts(words.as_ptr() as *const u8, *len)
+            },
+            ArchivedBytes::Owned(bytes) => bytes,
+            ArchivedBytes::Aligned(bytes) => bytes.as_slice(),
+        }
+    }
+}
+
+impl fmt::Display for ArchivedCallParametersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid archived call parameters: {}", self.0)
+    }
+}
+
+impl std::error::Error for ArchivedCallParametersError {}
+
+/// Takes from host current call parameters as an archived rkyv buffer and validates it.
+///
+/// The `get_call_parameters_rkyv` host function is provided only by the host API v4, so in Wasm
+/// modules this function is available only with the `host-api-v4` feature. Host allocates
+/// the archive by `allocate` with the `U64` element type, so it's aligned for the archived type
+/// and isn't copied.
+#[cfg(all(
+    feature = "marine-abi",
+    target_arch = "wasm32",
+    feature = "host-api-v4"
+))]
+pub fn get_archived_call_parameters(
+) -> Result<ArchivedCallParametersBuffer, ArchivedCallParametersError> {
+    const WORD_SIZE: usize = std::mem::size_of::<u64>();
+
+    // it's safe until it is executed on standard Fluence node with appropriate import function
+    let (words, len) = unsafe {
+        get_call_parameters_rkyv();
+        let ptr = crate::internal::get_result_ptr();
+        let len = crate::internal::get_result_size();
+        // host allocates exactly this number of words, so the archive could be freed as a Vec
+        let words_count = (len + WORD_SIZE - 1) / WORD_SIZE;
+        match words_count {
+            0 => {
+                // allocate returns 0 for empty allocations, so there is nothing to free
+                debug_assert_eq!(ptr, 0, "empty archive should be passed with a zero pointer");
+                (Vec::new(), 0)
+            }
+            _ => (
+                Vec::from_raw_parts(ptr as *mut u64, words_count, words_count),
+                len,
+            ),
+        }
+    };
+
+    ArchivedCallParametersBuffer::from_words(words, len)
+}
+
+#[cfg(not(all(target_arch = "wasm32", feature = "marine-abi")))]
+pub fn get_archived_call_parameters(
+) -> Result<ArchivedCallParametersBuffer, ArchivedCallParametersError> {
+    Err(ArchivedCallParametersError(
+        "call parameters are provided by host only to Wasm modules".to_string(),
+    ))
+}
+
+#[cfg(all(
+    feature = "marine-abi",
+    target_arch = "wasm32",
+    feature = "host-api-v4"
+))]
+marine_host_api::host_api_imports! {
+    extern "C" {
+        // sets a result pointer to archived current call parameters allocated as u64 words
+        // and a result size to the archive size in bytes
+        #[link_name = "get_call_parameters_rkyv"]
+        fn get_call_parameters_rkyv();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArchivedBytes;
+    use super::ArchivedCallParametersBuffer;
+    use crate::ArchivedCallParameters;
+    use crate::CallParameters;
+    use crate::SecurityTetraplet;
+
+    fn call_parameters() -> CallParameters {
+        let mut call_parameters = CallParameters {
+            service_id: "service".to_string(),
+            tetraplets: vec![vec![SecurityTetraplet::new("peer", "service", "fn", ".$")]],
+            ..<_>::default()
+        };
+        call_parameters.particle.script = "(seq (null) (null))".to_string();
+        call_parameters.particle.ttl = 100;
+        call_parameters
+    }
+
+    #[test]
+    fn archived_call_parameters() {
+        let bytes = rkyv::to_bytes::<_, 256>(&call_parameters()).unwrap();
+
+        let aligned_bytes = ArchivedBytes::new(bytes.to_vec(), 1);
+        assert!(matches!(aligned_bytes, ArchivedBytes::Owned(_)));
+        // no allocation is aligned to 1 GiB, so it's surely copied
+        let misaligned_bytes = ArchivedBytes::new(bytes.to_vec(), 1 << 30);
+        assert!(matches!(misaligned_bytes, ArchivedBytes::Aligned(_)));
+
+        for bytes in [aligned_bytes, misaligned_bytes] {
+            let buffer = ArchivedCallParametersBuffer::validate(bytes).unwrap();
+            let archived = buffer.get();
+            assert_eq!(archived.service_id, "service");
+            assert_eq!(archived.particle.script, "(seq (null) (null))");
+            assert_eq!(archived.particle.ttl, 100);
+            assert_eq!(archived.tetraplets[0][0].lens, ".$");
+        }
+    }
+
+    #[test]
+    fn archived_call_parameters_in_words() {
+        assert!(std::mem::align_of::<ArchivedCallParameters>() <= std::mem::align_of::<u64>());
+
+        let bytes = rkyv::to_bytes::<_, 256>(&call_parameters()).unwrap();
+        let mut words = vec![0u64; (bytes.len() + 7) / 8];
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks(8)) {
+            let mut word_bytes = [0u8; 8];
+            word_bytes[..chunk.len()].copy_from_slice(chunk);
+            *word = u64::from_ne_bytes(word_bytes);
+        }
+
+        let buffer = ArchivedCallParametersBuffer::from_words(words.clone(), bytes.len()).unwrap();
+        assert_eq!(buffer.get().service_id, "service");
+        assert_eq!(buffer.get().tetraplets[0][0].peer_pk, "peer");
+
+        let too_long = ArchivedCallParametersBuffer::from_words(words, bytes.len() + 8);
+        assert!(too_long.is_err());
+    }
+
+    #[test]
+    fn invalid_archived_call_parameters() {
+        assert!(ArchivedCallParametersBuffer::from_bytes(vec![1, 2, 3]).is_err());
+    }
+}
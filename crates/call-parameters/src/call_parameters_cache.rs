@@ -0,0 +1,291 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Call parameters are taken from host once per export function call and cached until
+//! the end of this call, so accessors of particular fields share one copy of them. With the `rkyv`
+//! and `host-api-v4` features this copy is archived by rkyv, so accessors don't deserialize
+//! the script and tetraplets of other arguments, unless call parameters have already been
+//! deserialized during this call. The code generated by `#[marine]` resets the cache when
+//! an export function is called and when it returns, exports written by hand should call
+//! [`reset_call_parameters_cache`] or [`get_call_parameters`](crate::get_call_parameters)
+//! at their beginning.
+
+#[cfg(all(
+    feature = "rkyv",
+    any(
+        feature = "host-api-v4",
+        not(all(feature = "marine-abi", target_arch = "wasm32"))
+    )
+))]
+use archived_cache::with_call_parameters;
+use crate::CallParameters;
+use crate::SecurityTetraplet;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+thread_local! {
+    static CACHED_CALL_PARAMETERS: RefCell<Option<Rc<CallParameters>>> = RefCell::new(None);
+}
+
+/// Evaluates the first expression with archived call parameters if they are available,
+/// otherwise the second one with deserialized call parameters.
+#[cfg(all(
+    feature = "rkyv",
+    any(
+        feature = "host-api-v4",
+        not(all(feature = "marine-abi", target_arch = "wasm32"))
+    )
+))]
+macro_rules! with_call_parameters {
+    (|$call_parameters:ident| $expr:expr) => {
+        with_call_parameters!(|$call_parameters| $expr, |$call_parameters| $expr)
+    };
+    (|$archived:ident| $from_archived:expr, |$call_parameters:ident| $from_deserialized:expr) => {
+        with_call_parameters(
+            |$archived| $from_archived,
+            |$call_parameters| $from_deserialized,
+        )
+    };
+}
+
+#[cfg(not(all(
+    feature = "rkyv",
+    any(
+        feature = "host-api-v4",
+        not(all(feature = "marine-abi", target_arch = "wasm32"))
+    )
+)))]
+macro_rules! with_call_parameters {
+    (|$call_parameters:ident| $expr:expr) => {
+        with_call_parameters!(|$call_parameters| $expr, |$call_parameters| $expr)
+    };
+    (|$archived:ident| $from_archived:expr, |$call_parameters:ident| $from_deserialized:expr) => {{
+        let $call_parameters = &*get_cached_call_parameters();
+        $from_deserialized
+    }};
+}
+
+/// Returns call parameters of the current export function call, they are taken from host
+/// on the first call and shared without copying after that.
+pub fn get_cached_call_parameters() -> Rc<CallParameters> {
+    get_or_fetch(crate::fetch_call_parameters)
+}
+
+/// Returns id of the current particle.
+pub fn get_particle_id() -> String {
+    with_call_parameters!(|call_parameters| call_parameters.particle.id.as_str().to_string())
+}
+
+/// Returns peer id of the current particle initiator.
+pub fn get_init_peer_id() -> String {
+    with_call_parameters!(|call_parameters| call_parameters
+        .particle
+        .init_peer_id
+        .as_str()
+        .to_string())
+}
+
+/// Returns id of the current service.
+pub fn get_service_id() -> String {
+    with_call_parameters!(|call_parameters| call_parameters.service_id.as_str().to_string())
+}
+
+/// Returns peer id of the current service creator.
+pub fn get_service_creator_peer_id() -> String {
+    with_call_parameters!(|call_parameters| call_parameters
+        .service_creator_peer_id
+        .as_str()
+        .to_string())
+}
+
+/// Returns peer id of the host.
+pub fn get_host_id() -> String {
+    with_call_parameters!(|call_parameters| call_parameters.host_id.as_str().to_string())
+}
+
+/// Returns peer id of the worker.
+pub fn get_worker_id() -> String {
+    with_call_parameters!(|call_parameters| call_parameters.worker_id.as_str().to_string())
+}
+
+/// Returns tetraplets of an argument with the given index, if there are any.
+/// Tetraplets of other arguments aren't deserialized if call parameters are archived.
+pub fn get_tetraplets(arg_index: usize) -> Option<Vec<SecurityTetraplet>> {
+    with_call_parameters!(
+        |archived| {
+            use rkyv::Deserialize;
+
+            archived
+                .tetraplets
+                .get(arg_index)
+                .and_then(|tetraplets| tetraplets.deserialize(&mut rkyv::Infallible).ok())
+        },
+        |call_parameters| call_parameters.tetraplets.get(arg_index).cloned()
+    )
+}
+
+/// Drops cached call parameters, so they will be taken from host again on the next access.
+/// It's called by the code generated by `#[marine]`, exports written by hand should call it
+/// at their beginning.
+pub fn reset_call_parameters_cache() {
+    CACHED_CALL_PARAMETERS.with(|cache| cache.borrow_mut().take());
+    #[cfg(all(
+        feature = "rkyv",
+        any(
+            feature = "host-api-v4",
+            not(all(feature = "marine-abi", target_arch = "wasm32"))
+        )
+    ))]
+    archived_cache::reset();
+}
+
+fn get_or_fetch(fetch: impl FnOnce() -> CallParameters) -> Rc<CallParameters> {
+    CACHED_CALL_PARAMETERS.with(|cache| {
+        cache
+            .borrow_mut()
+            .get_or_insert_with(|| Rc::new(fetch()))
+            .clone()
+    })
+}
+
+#[cfg(all(
+    feature = "rkyv",
+    any(
+        feature = "host-api-v4",
+        not(all(feature = "marine-abi", target_arch = "wasm32"))
+    )
+))]
+mod archived_cache {
+    use super::get_or_fetch;
+    use super::CACHED_CALL_PARAMETERS;
+    use crate::ArchivedCallParameters;
+    use crate::ArchivedCallParametersBuffer;
+    use crate::ArchivedCallParametersError;
+    use crate::CallParameters;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    thread_local! {
+        static CACHED_ARCHIVED_CALL_PARAMETERS: RefCell<Option<Rc<ArchivedCallParametersBuffer>>> =
+            RefCell::new(None);
+    }
+
+    pub(super) fn with_call_parameters<T>(
+        from_archived: impl FnOnce(&ArchivedCallParameters) -> T,
+        from_deserialized: impl FnOnce(&CallParameters) -> T,
+    ) -> T {
+        with_fetched(
+            crate::get_archived_call_parameters,
+            crate::fetch_call_parameters,
+            from_archived,
+            from_deserialized,
+        )
+    }
+
+    pub(super) fn reset() {
+        CACHED_ARCHIVED_CALL_PARAMETERS.with(|cache| cache.borrow_mut().take());
+    }
+
+    pub(super) fn with_fetched<T>(
+        fetch_archived: impl FnOnce()
+            -> Result<ArchivedCallParametersBuffer, ArchivedCallParametersError>,
+        fetch: impl FnOnce() -> CallParameters,
+        from_archived: impl FnOnce(&ArchivedCallParameters) -> T,
+        from_deserialized: impl FnOnce(&CallParameters) -> T,
+    ) -> T {
+        // already deserialized call parameters are used instead of taking them from host again
+        if let Some(call_parameters) = CACHED_CALL_PARAMETERS.with(|cache| cache.borrow().clone()) {
+            return from_deserialized(&call_parameters);
+        }
+
+        let archived = CACHED_ARCHIVED_CALL_PARAMETERS.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if cache.is_none() {
+                *cache = fetch_archived().ok().map(Rc::new);
+            }
+            cache.clone()
+        });
+
+        match archived {
+            Some(archived) => from_archived(archived.get()),
+            // host couldn't provide archived call parameters, so they are deserialized as usual
+            None => from_deserialized(&get_or_fetch(fetch)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_or_fetch;
+    use super::reset_call_parameters_cache;
+    use crate::CallParameters;
+
+    use std::cell::Cell;
+
+    #[test]
+    fn fetched_once_until_reset() {
+        let fetch_count = Cell::new(0);
+        let fetch = || {
+            fetch_count.set(fetch_count.get() + 1);
+            CallParameters {
+                service_id: format!("service_{}", fetch_count.get()),
+                ..<_>::default()
+            }
+        };
+
+        assert_eq!(get_or_fetch(fetch).service_id, "service_1");
+        assert_eq!(get_or_fetch(fetch).service_id, "service_1");
+        assert_eq!(fetch_count.get(), 1);
+
+        reset_call_parameters_cache();
+        assert_eq!(get_or_fetch(fetch).service_id, "service_2");
+        assert_eq!(fetch_count.get(), 2);
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn archived_are_used_until_deserialized() {
+        use super::archived_cache::with_fetched;
+        use crate::ArchivedCallParametersBuffer;
+
+        let call_parameters = CallParameters {
+            service_id: "archived".to_string(),
+            ..<_>::default()
+        };
+        let archive = rkyv::to_bytes::<_, 256>(&call_parameters).unwrap().to_vec();
+        let service_id = |archive: Vec<u8>| {
+            with_fetched(
+                || ArchivedCallParametersBuffer::from_bytes(archive),
+                || CallParameters {
+                    service_id: "deserialized".to_string(),
+                    ..<_>::default()
+                },
+                |archived| archived.service_id.to_string(),
+                |call_parameters| call_parameters.service_id.clone(),
+            )
+        };
+
+        reset_call_parameters_cache();
+        assert_eq!(service_id(archive.clone()), "archived");
+        assert_eq!(service_id(vec![]), "archived");
+
+        reset_call_parameters_cache();
+        assert_eq!(service_id(vec![1, 2, 3]), "deserialized");
+        assert_eq!(service_id(archive), "deserialized");
+    }
+}
@@ -0,0 +1,305 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A parsed representation of [`SecurityTetraplet::lens`], which describes how a value was
+//! extracted from the output of a service call.
+//!
+//! A lens is a sequence of the following segments:
+//!  - `.$` - start of the functor applied to a value,
+//!  - `.field` - access to a field of an object,
+//!  - `.[5]` - access to an array element by index,
+//!  - `.[idx]` - access to an element or field by a value of the variable,
+//!  - `[5]` - access to a stream element by index.
+//!
+//! [`SecurityTetraplet::lens`]: crate::SecurityTetraplet::lens
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A parsed lens, see the module documentation for the supported syntax.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Lens {
+    pub segments: Vec<LensSegment>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum LensSegment {
+    /// `.$`
+    Functor,
+    /// `.field`
+    Field(String),
+    /// `.[5]`
+    Index(u32),
+    /// `.[idx]`
+    Variable(String),
+    /// `[5]`
+    StreamIndex(u32),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LensParseError {
+    /// A character that can't appear at this position.
+    UnexpectedChar { position: usize, found: char },
+    /// The lens ends in the middle of a segment.
+    UnexpectedEnd,
+    /// A field or variable name is empty.
+    EmptyName { position: usize },
+    /// An index doesn't fit into u32.
+    IndexOverflow { position: usize },
+}
+
+impl Lens {
+    pub fn parse(lens: &str) -> Result<Self, LensParseError> {
+        LensParser::new(lens).parse()
+    }
+
+    /// Returns true if no lens was applied to a value.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
+
+impl FromStr for Lens {
+    type Err = LensParseError;
+
+    fn from_str(lens: &str) -> Result<Self, Self::Err> {
+        Self::parse(lens)
+    }
+}
+
+impl fmt::Display for Lens {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.segments
+            .iter()
+            .try_for_each(|segment| write!(f, "{}", segment))
+    }
+}
+
+impl fmt::Display for LensSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LensSegment::Functor => write!(f, ".$"),
+            LensSegment::Field(name) => write!(f, ".{}", name),
+            LensSegment::Index(index) => write!(f, ".[{}]", index),
+            LensSegment::Variable(name) => write!(f, ".[{}]", name),
+            LensSegment::StreamIndex(index) => write!(f, "[{}]", index),
+        }
+    }
+}
+
+impl fmt::Display for LensParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LensParseError::UnexpectedChar { position, found } => {
+                write!(f, "unexpected character '{}' at {}", found, position)
+            }
+            LensParseError::UnexpectedEnd => write!(f, "lens ends unexpectedly"),
+            LensParseError::EmptyName { position } => write!(f, "empty name at {}", position),
+            LensParseError::IndexOverflow { position } => {
+                write!(f, "index at {} doesn't fit into u32", position)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LensParseError {}
+
+struct LensParser<'l> {
+    lens: &'l str,
+    position: usize,
+}
+
+impl<'l> LensParser<'l> {
+    fn new(lens: &'l str) -> Self {
+        Self { lens, position: 0 }
+    }
+
+    fn parse(mut self) -> Result<Lens, LensParseError> {
+        let mut segments = Vec::new();
+        while let Some(ch) = self.peek() {
+            let segment = match ch {
+                '.' => {
+                    self.position += 1;
+                    self.parse_dotted_segment()?
+                }
+                '[' => {
+                    let start = self.position;
+                    self.position += 1;
+                    match self.parse_bracketed()? {
+                        LensSegment::Index(index) => LensSegment::StreamIndex(index),
+                        // variables are allowed only after a dot
+                        _ => return Err(self.unexpected_char_at(start)),
+                    }
+                }
+                found => {
+                    return Err(LensParseError::UnexpectedChar {
+                        position: self.position,
+                        found,
+                    })
+                }
+            };
+            segments.push(segment);
+        }
+
+        Ok(Lens { segments })
+    }
+
+    fn parse_dotted_segment(&mut self) -> Result<LensSegment, LensParseError> {
+        match self.peek() {
+            Some('$') => {
+                self.position += 1;
+                Ok(LensSegment::Functor)
+            }
+            Some('[') => {
+                self.position += 1;
+                self.parse_bracketed()
+            }
+            Some(_) => self.parse_name().map(LensSegment::Field),
+            None => Err(LensParseError::UnexpectedEnd),
+        }
+    }
+
+    /// Parses an index or a variable name with the closing bracket.
+    fn parse_bracketed(&mut self) -> Result<LensSegment, LensParseError> {
+        let start = self.position;
+        let segment = match self.peek() {
+            Some(ch) if ch.is_ascii_digit() => {
+                let digits = self.take_while(|ch| ch.is_ascii_digit());
+                let index = digits
+                    .parse()
+                    .map_err(|_| LensParseError::IndexOverflow { position: start })?;
+                LensSegment::Index(index)
+            }
+            Some(_) => LensSegment::Variable(self.parse_name()?),
+            None => return Err(LensParseError::UnexpectedEnd),
+        };
+
+        match self.peek() {
+            Some(']') => {
+                self.position += 1;
+                Ok(segment)
+            }
+            Some(_) => Err(self.unexpected_char_at(self.position)),
+            None => Err(LensParseError::UnexpectedEnd),
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, LensParseError> {
+        let start = self.position;
+        let name = self.take_while(|ch| ch.is_alphanumeric() || ch == '_' || ch == '-');
+        if name.is_empty() {
+            return match self.peek() {
+                Some(ch) if ch == '.' || ch == '[' || ch == ']' => {
+                    Err(LensParseError::EmptyName { position: start })
+                }
+                Some(_) => Err(self.unexpected_char_at(start)),
+                None => Err(LensParseError::UnexpectedEnd),
+            };
+        }
+
+        Ok(name.to_string())
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'l str {
+        let rest = &self.lens[self.position..];
+        let len = rest
+            .char_indices()
+            .find(|&(_, ch)| !predicate(ch))
+            .map(|(len, _)| len)
+            .unwrap_or(rest.len());
+        self.position += len;
+
+        &rest[..len]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.lens[self.position..].chars().next()
+    }
+
+    fn unexpected_char_at(&self, position: usize) -> LensParseError {
+        let found = self.lens[position..].chars().next().unwrap_or_default();
+        LensParseError::UnexpectedChar { position, found }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lens;
+    use super::LensParseError;
+    use super::LensSegment;
+
+    #[test]
+    fn parse_all_segments() {
+        let lens = Lens::parse("[2].$.peers.[0].[idx].peer_id").unwrap();
+        let expected_segments = vec![
+            LensSegment::StreamIndex(2),
+            LensSegment::Functor,
+            LensSegment::Field("peers".to_string()),
+            LensSegment::Index(0),
+            LensSegment::Variable("idx".to_string()),
+            LensSegment::Field("peer_id".to_string()),
+        ];
+
+        assert_eq!(lens.segments, expected_segments);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for lens in [
+            "",
+            ".$",
+            ".$.[1]",
+            "[0]",
+            ".$.field.[var_1].[10]",
+            ".$.a-b.c_d",
+        ] {
+            let parsed = Lens::parse(lens).unwrap();
+            assert_eq!(parsed.to_string(), lens);
+            assert_eq!(Lens::parse(&parsed.to_string()).unwrap(), parsed);
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Lens::parse(".$."), Err(LensParseError::UnexpectedEnd));
+        assert_eq!(Lens::parse(".$.[1"), Err(LensParseError::UnexpectedEnd));
+        assert_eq!(
+            Lens::parse(".$..a"),
+            Err(LensParseError::EmptyName { position: 3 })
+        );
+        assert_eq!(
+            Lens::parse("$.a"),
+            Err(LensParseError::UnexpectedChar {
+                position: 0,
+                found: '$'
+            })
+        );
+        assert_eq!(
+            Lens::parse("[idx]"),
+            Err(LensParseError::UnexpectedChar {
+                position: 0,
+                found: '['
+            })
+        );
+        assert_eq!(
+            Lens::parse(".[99999999999]"),
+            Err(LensParseError::IndexOverflow { position: 2 })
+        );
+    }
+}
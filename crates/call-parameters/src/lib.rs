@@ -14,6 +14,42 @@
  * limitations under the License.
  */
 
+#[cfg(feature = "rkyv")]
+mod archived_call_parameters;
+mod call_parameters_cache;
+mod lens;
+#[cfg(feature = "crypto")]
+mod particle_signature;
+
+#[cfg(all(
+    feature = "rkyv",
+    any(
+        feature = "host-api-v4",
+        not(all(feature = "marine-abi", target_arch = "wasm32"))
+    )
+))]
+pub use archived_call_parameters::get_archived_call_parameters;
+#[cfg(feature = "rkyv")]
+pub use archived_call_parameters::ArchivedCallParametersBuffer;
+#[cfg(feature = "rkyv")]
+pub use archived_call_parameters::ArchivedCallParametersError;
+pub use call_parameters_cache::get_cached_call_parameters;
+pub use call_parameters_cache::get_host_id;
+pub use call_parameters_cache::get_init_peer_id;
+pub use call_parameters_cache::get_particle_id;
+pub use call_parameters_cache::get_service_creator_peer_id;
+pub use call_parameters_cache::get_service_id;
+pub use call_parameters_cache::get_tetraplets;
+pub use call_parameters_cache::get_worker_id;
+pub use call_parameters_cache::reset_call_parameters_cache;
+pub use lens::Lens;
+pub use lens::LensParseError;
+pub use lens::LensSegment;
+#[cfg(feature = "crypto")]
+pub use particle_signature::ed25519_public_key;
+#[cfg(feature = "crypto")]
+pub use particle_signature::ParticleSignatureError;
+
 #[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
 use marine_macro::marine;
 
@@ -73,6 +109,30 @@ impl SecurityTetraplet {
     pub fn add_lens(&mut self, lens: &str) {
         self.lens.push_str(lens)
     }
+
+    /// Returns true if this tetraplet describes a string literal defined in the script,
+    /// see [`SecurityTetraplet::literal_tetraplet`].
+    pub fn is_literal(&self) -> bool {
+        self.service_id.is_empty() && self.function_name.is_empty() && self.lens.is_empty()
+    }
+
+    /// Returns true if the corresponding value was returned by the given function of the given
+    /// service on the given peer. Note that a lens could be applied to it after that.
+    pub fn originates_from(&self, peer_pk: &str, service_id: &str, function_name: &str) -> bool {
+        self.peer_pk == peer_pk
+            && self.service_id == service_id
+            && self.function_name == function_name
+    }
+
+    /// Returns true if a lens was applied to the corresponding value.
+    pub fn has_lens(&self) -> bool {
+        !self.lens.is_empty()
+    }
+
+    /// Parses the lens applied to the corresponding value.
+    pub fn parsed_lens(&self) -> Result<Lens, LensParseError> {
+        Lens::parse(&self.lens)
+    }
 }
 
 /// This struct contains parameters that would be accessible by Wasm modules.
@@ -133,6 +193,24 @@ pub struct ParticleParameters {
     pub token: String,
 }
 
+impl ParticleParameters {
+    /// Unix timestamp in milliseconds after which the particle is expired.
+    pub fn deadline(&self) -> u64 {
+        self.timestamp.saturating_add(self.ttl as u64)
+    }
+
+    /// Milliseconds left until the particle is expired, `now` is a unix timestamp in milliseconds,
+    /// e.g. returned by `get_current_time`.
+    pub fn remaining_ttl(&self, now: u64) -> u64 {
+        self.deadline().saturating_sub(now)
+    }
+
+    /// Returns true if the particle is expired at `now`, which is a unix timestamp in milliseconds.
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.deadline()
+    }
+}
+
 use std::fmt;
 impl fmt::Display for SecurityTetraplet {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -144,10 +222,19 @@ impl fmt::Display for SecurityTetraplet {
     }
 }
 
-/// This functions takes from host current call parameters.
-/// Beware that this implies import function call which takes some time.
-#[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
+/// This functions takes from host current call parameters, beware that this implies
+/// import function call which takes some time. It also drops call parameters cached by
+/// [`get_cached_call_parameters`] and accessors of particular fields like [`get_init_peer_id`],
+/// so they don't return parameters of a previous call in exports not generated by `#[marine]`.
 pub fn get_call_parameters() -> CallParameters {
+    reset_call_parameters_cache();
+    fetch_call_parameters()
+}
+
+/// Takes call parameters from host, beware that this implies import function call
+/// which takes some time.
+#[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
+fn fetch_call_parameters() -> CallParameters {
     // it's safe until it is executed on standard Fluence node with appropriate import function
     unsafe {
         get_call_raw_parameters();
@@ -157,17 +244,56 @@ pub fn get_call_parameters() -> CallParameters {
 }
 
 #[cfg(not(all(target_arch = "wasm32", feature = "marine-abi")))]
-pub fn get_call_parameters() -> CallParameters {
+fn fetch_call_parameters() -> CallParameters {
     unimplemented!()
 }
 
+/// Returns the current unix time in milliseconds provided by the host,
+/// so it doesn't depend on WASI clocks of a module.
+///
+/// The `get_current_time` host function is provided only by the host API v4, so in Wasm modules
+/// this function is available only with the `host-api-v4` feature.
+#[cfg(all(
+    feature = "marine-abi",
+    target_arch = "wasm32",
+    feature = "host-api-v4"
+))]
+pub fn get_current_time() -> u64 {
+    // it's safe until it is executed on standard Fluence node with appropriate import function
+    unsafe { get_current_time_impl() }
+}
+
+#[cfg(not(all(target_arch = "wasm32", feature = "marine-abi")))]
+pub fn get_current_time() -> u64 {
+    use std::time::SystemTime;
+
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
 #[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
-#[link(wasm_import_module = "__marine_host_api_v3")]
-#[allow(improper_ctypes)]
-extern "C" {
-    // returns serialized current call parameters
-    #[link_name = "get_call_parameters"]
-    fn get_call_raw_parameters();
+marine_host_api::host_api_imports! {
+    #[allow(improper_ctypes)]
+    extern "C" {
+        // returns serialized current call parameters
+        #[link_name = "get_call_parameters"]
+        fn get_call_raw_parameters();
+    }
+}
+
+#[cfg(all(
+    feature = "marine-abi",
+    target_arch = "wasm32",
+    feature = "host-api-v4"
+))]
+marine_host_api::host_api_imports! {
+    extern "C" {
+        // returns the current unix time in milliseconds
+        #[link_name = "get_current_time"]
+        fn get_current_time_impl() -> u64;
+    }
 }
 
 #[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
@@ -183,3 +309,41 @@ mod internal {
     pub(crate) use marine_rs_sdk_main::set_result_ptr;
     pub(crate) use marine_rs_sdk_main::set_result_size;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ParticleParameters;
+    use super::SecurityTetraplet;
+
+    #[test]
+    fn tetraplet_predicates() {
+        let literal = SecurityTetraplet::literal_tetraplet("init_peer");
+        assert!(literal.is_literal());
+        assert!(!literal.has_lens());
+
+        let mut tetraplet = SecurityTetraplet::new("peer", "service", "function", "");
+        assert!(!tetraplet.is_literal());
+        assert!(tetraplet.originates_from("peer", "service", "function"));
+        assert!(!tetraplet.originates_from("peer", "service", "other_function"));
+
+        tetraplet.add_lens(".$.[0]");
+        assert!(tetraplet.has_lens());
+        assert!(tetraplet.originates_from("peer", "service", "function"));
+        assert_eq!(tetraplet.parsed_lens().unwrap().to_string(), ".$.[0]");
+    }
+
+    #[test]
+    fn particle_expiry() {
+        let particle = ParticleParameters {
+            timestamp: 1000,
+            ttl: 500,
+            ..<_>::default()
+        };
+
+        assert_eq!(particle.deadline(), 1500);
+        assert_eq!(particle.remaining_ttl(1200), 300);
+        assert_eq!(particle.remaining_ttl(2000), 0);
+        assert!(!particle.is_expired(1499));
+        assert!(particle.is_expired(1500));
+    }
+}
@@ -0,0 +1,189 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Verification of particle signatures made by the particle initiator.
+//!
+//! A particle is signed by the private key of its init peer, so the signature could be checked
+//! with the public key extracted from `init_peer_id` without trusting the host.
+//! At the moment, only ed25519 keys are supported, peer ids of such keys contain the public key
+//! itself encoded with the identity multihash.
+
+use crate::ParticleParameters;
+
+use ed25519_dalek::Signature;
+use ed25519_dalek::VerifyingKey;
+
+use std::fmt;
+
+/// Prefix of an ed25519 peer id: the identity multihash code, the multihash length (36 bytes),
+/// then the protobuf encoded public key with the key type field (Ed25519 = 1) and
+/// the data field of 32 bytes.
+const ED25519_PEER_ID_PREFIX: [u8; 6] = [0x00, 0x24, 0x08, 0x01, 0x12, 0x20];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParticleSignatureError {
+    /// The peer id isn't a valid base58 string.
+    InvalidBase58(String),
+    /// The peer id doesn't contain an ed25519 public key.
+    UnsupportedPeerId(String),
+    /// The public key encoded in the peer id isn't a valid ed25519 point.
+    InvalidPublicKey(String),
+    /// The signature doesn't have the ed25519 signature length.
+    InvalidSignatureLength(usize),
+    /// The signature doesn't match the particle.
+    VerificationFailed,
+}
+
+impl ParticleParameters {
+    /// Returns the bytes signed by the particle initiator: id, init_peer_id, timestamp and ttl
+    /// in little endian, and script.
+    pub fn signed_bytes(&self) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(self.id.len() + self.init_peer_id.len() + 8 + 4 + self.script.len());
+        bytes.extend_from_slice(self.id.as_bytes());
+        bytes.extend_from_slice(self.init_peer_id.as_bytes());
+        bytes.extend_from_slice(&self.timestamp.to_le_bytes());
+        bytes.extend_from_slice(&self.ttl.to_le_bytes());
+        bytes.extend_from_slice(self.script.as_bytes());
+
+        bytes
+    }
+
+    /// Checks that the particle was signed by the private key of init_peer_id.
+    pub fn verify_signature(&self) -> Result<(), ParticleSignatureError> {
+        let public_key = ed25519_public_key(&self.init_peer_id)?;
+        let verifying_key = VerifyingKey::from_bytes(&public_key)
+            .map_err(|e| ParticleSignatureError::InvalidPublicKey(e.to_string()))?;
+
+        let signature = Signature::from_slice(&self.signature)
+            .map_err(|_| ParticleSignatureError::InvalidSignatureLength(self.signature.len()))?;
+
+        verifying_key
+            .verify_strict(&self.signed_bytes(), &signature)
+            .map_err(|_| ParticleSignatureError::VerificationFailed)
+    }
+}
+
+/// Extracts an ed25519 public key from a base58 encoded peer id.
+pub fn ed25519_public_key(peer_id: &str) -> Result<[u8; 32], ParticleSignatureError> {
+    let peer_id_bytes = bs58::decode(peer_id)
+        .into_vec()
+        .map_err(|e| ParticleSignatureError::InvalidBase58(e.to_string()))?;
+
+    peer_id_bytes
+        .strip_prefix(&ED25519_PEER_ID_PREFIX)
+        .and_then(|public_key| public_key.try_into().ok())
+        .ok_or_else(|| ParticleSignatureError::UnsupportedPeerId(peer_id.to_string()))
+}
+
+impl fmt::Display for ParticleSignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParticleSignatureError::InvalidBase58(error) => {
+                write!(f, "peer id isn't a valid base58 string: {}", error)
+            }
+            ParticleSignatureError::UnsupportedPeerId(peer_id) => {
+                write!(
+                    f,
+                    "peer id {} doesn't contain an ed25519 public key",
+                    peer_id
+                )
+            }
+            ParticleSignatureError::InvalidPublicKey(error) => {
+                write!(f, "invalid ed25519 public key: {}", error)
+            }
+            ParticleSignatureError::InvalidSignatureLength(length) => {
+                write!(f, "signature should be 64 bytes long, but it's {}", length)
+            }
+            ParticleSignatureError::VerificationFailed => {
+                write!(f, "signature doesn't match the particle")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParticleSignatureError {}
+
+#[cfg(test)]
+mod tests {
+    use super::ed25519_public_key;
+    use super::ParticleSignatureError;
+    use super::ED25519_PEER_ID_PREFIX;
+    use crate::ParticleParameters;
+
+    use ed25519_dalek::Signer;
+    use ed25519_dalek::SigningKey;
+
+    fn signed_particle(signing_key: &SigningKey) -> ParticleParameters {
+        let mut peer_id = ED25519_PEER_ID_PREFIX.to_vec();
+        peer_id.extend_from_slice(signing_key.verifying_key().as_bytes());
+
+        let mut particle = ParticleParameters {
+            id: "particle_id".to_string(),
+            init_peer_id: bs58::encode(peer_id).into_string(),
+            timestamp: 1700000000000,
+            ttl: 30000,
+            script: "(null)".to_string(),
+            ..<_>::default()
+        };
+        particle.signature = signing_key.sign(&particle.signed_bytes()).to_vec();
+
+        particle
+    }
+
+    #[test]
+    fn valid_signature() {
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let particle = signed_particle(&signing_key);
+
+        assert!(particle.init_peer_id.starts_with("12D3KooW"));
+        assert_eq!(particle.verify_signature(), Ok(()));
+    }
+
+    #[test]
+    fn tampered_particle() {
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let mut particle = signed_particle(&signing_key);
+        particle.ttl += 1;
+
+        assert_eq!(
+            particle.verify_signature(),
+            Err(ParticleSignatureError::VerificationFailed)
+        );
+
+        particle.signature.pop();
+        assert_eq!(
+            particle.verify_signature(),
+            Err(ParticleSignatureError::InvalidSignatureLength(63))
+        );
+    }
+
+    #[test]
+    fn unsupported_peer_id() {
+        // an RSA peer id uses the sha256 multihash instead of the identity one
+        let peer_id = "QmcEPrat8ShnCph8WjkREzt5CPXF2RwhYxYBALDcLC1iV6";
+        assert_eq!(
+            ed25519_public_key(peer_id),
+            Err(ParticleSignatureError::UnsupportedPeerId(
+                peer_id.to_string()
+            ))
+        );
+        assert!(matches!(
+            ed25519_public_key("0OIl"),
+            Err(ParticleSignatureError::InvalidBase58(_))
+        ));
+    }
+}
@@ -0,0 +1,213 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The single source of truth for the version of the host API modules are built against.
+//!
+//! Host functions are imported from the `__marine_host_api_v<version>` namespace, so both
+//! the SDK crates and the `#[marine]` macro take the namespace and the version from here.
+//! The namespace is kept as a literal inside `with_host_api_namespace!` because
+//! `#[link(wasm_import_module = ...)]` accepts only literals.
+//!
+//! The version is selected by either the `host-api-v3` or the `host-api-v4` feature, v3 is used
+//! if none is selected. Enabling both of them is a compile error, because a module could import
+//! host functions from only one namespace. Signatures of host functions present in both versions
+//! are the same, so the public SDK API doesn't depend on the selected version.
+//!
+//! Hosts of the v3 provide `get_call_parameters` and `log_utf8_string`, v4 hosts additionally
+//! provide the following functions, SDK functions using them are available only with `host-api-v4`:
+//! - `get_current_time() -> u64` returns the current unix time in milliseconds
+//! - `log_utf8_batch(batch_ptr: i32, batch_size: i32)` writes several messages to a logger
+//!   in one call, the batch is a sequence of records each consisting of `level: i32`,
+//!   `target: i32` and `msg_size: u32` in little-endian followed by `msg_size` bytes of
+//!   a UTF-8 message, they are written in order the same way as by `log_utf8_string`
+//! - `get_call_parameters_rkyv()` sets the result pointer to current call parameters archived
+//!   by rkyv and the result size to the archive size in bytes, the archive is allocated by
+//!   `allocate` with the `U64` element type and exactly `ceil(size / 8)` elements to be aligned
+//!   for archived types and freed by the module, an empty archive is passed with a zero pointer
+//! - `mounted_binary_spawn(binary_ptr: u32, binary_size: u32, args_ptr: u32, args_size: u32,
+//!   stdout_limit: u64, stderr_limit: u64) -> i64` spawns a mounted binary with arguments each
+//!   terminated by '\0' and keeps at most the given number of bytes of its output,
+//!   returns a non-negative handle or a negative error code
+//! - `mounted_binary_read(handle: i64, stream: u32, buf_ptr: u32, buf_size: u32) -> i64` copies
+//!   the next chunk of stdout (0) or stderr (1) into the buffer and returns its size, 0 at the end
+//!   of the stream, -6 at the end of a stream truncated by its limit or another negative error code
+//! - `mounted_binary_wait(handle: i64) -> i32` waits for the binary and returns its return code
+//! - `mounted_binary_close(handle: i64)` releases the handle and the output kept for it
+//!
+//! Negative codes of the mounted binary functions follow the `BINARY_*_CODE` constants of
+//! the SDK, for a negative code host sets the result pointer and size to an error message.
+//!
+//! Modules of both versions also export `get_allocation_error() -> u32` next to `allocate`.
+//! A host could call it right after `allocate` returned 0 for a non-zero number of elements
+//! to find out why: 1 if the module is out of memory and 2 if the element type is unknown.
+//! Each `allocate` call resets it to 0, so it describes only the last allocation. Hosts aren't
+//! required to call it, the generated export functions detect failed allocations of their
+//! arguments by themselves.
+
+#![deny(
+    dead_code,
+    nonstandard_style,
+    unused_imports,
+    unused_mut,
+    unused_variables,
+    unused_unsafe,
+    unreachable_patterns
+)]
+#![warn(rust_2018_idioms)]
+
+#[cfg(all(feature = "host-api-v3", feature = "host-api-v4"))]
+compile_error!(
+    "features `host-api-v3` and `host-api-v4` are mutually exclusive, enable only one of them"
+);
+
+/// Passes the host API namespace literal as the first token to the given macro of this crate.
+#[cfg(not(feature = "host-api-v4"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! with_host_api_namespace {
+    ($callback:ident $($args:tt)*) => {
+        $crate::$callback! { "__marine_host_api_v3" $($args)* }
+    };
+}
+
+/// Passes the host API namespace literal as the first token to the given macro of this crate.
+#[cfg(feature = "host-api-v4")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! with_host_api_namespace {
+    ($callback:ident $($args:tt)*) => {
+        $crate::$callback! { "__marine_host_api_v4" $($args)* }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __namespace_literal {
+    ($namespace:literal) => {
+        $namespace
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __host_api_imports_with_namespace {
+    ($namespace:literal $(#[$attr:meta])* extern "C" { $($items:tt)* }) => {
+        $(#[$attr])*
+        #[link(wasm_import_module = $namespace)]
+        extern "C" {
+            $($items)*
+        }
+    };
+}
+
+/// Declares an extern block importing functions from the host API namespace.
+///
+/// ```ignore
+/// marine_host_api::host_api_imports! {
+///     extern "C" {
+///         fn get_current_time() -> u64;
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! host_api_imports {
+    ($($block:tt)*) => {
+        $crate::with_host_api_namespace! { __host_api_imports_with_namespace $($block)* }
+    };
+}
+
+/// Prefix of namespaces of all host API versions.
+pub const HOST_API_NAMESPACE_PREFIX: &str = "__marine_host_api_v";
+
+/// Namespace host functions are imported from.
+pub const HOST_API_NAMESPACE: &str = with_host_api_namespace!(__namespace_literal);
+
+/// Version of the host API modules are built against.
+pub const HOST_API_VERSION: u32 = version_from_namespace(HOST_API_NAMESPACE);
+
+/// Name of the custom section with the host API version required by a module.
+// TODO: avoid duplication with the link_section when key-value attributes become stable
+pub const HOST_API_VERSION_SECTION_NAME: &str = "__marine_host_api_version";
+
+/// Name of the optional export that returns the host API version required by a module.
+pub const HOST_API_VERSION_EXPORT_NAME: &str = "__marine_host_api_version";
+
+/// Returns content of the host API version section: `HOST_API_VERSION` as little-endian u32.
+/// Hosts provide only the namespace of their own version, so it's the only version
+/// able to run a module.
+pub const fn host_api_version_section() -> [u8; 4] {
+    HOST_API_VERSION.to_le_bytes()
+}
+
+const fn version_from_namespace(namespace: &str) -> u32 {
+    let namespace = namespace.as_bytes();
+    let prefix = HOST_API_NAMESPACE_PREFIX.as_bytes();
+    if namespace.len() <= prefix.len() {
+        panic!("host API namespace must contain a version");
+    }
+
+    let mut byte_id = 0;
+    while byte_id < prefix.len() {
+        if namespace[byte_id] != prefix[byte_id] {
+            panic!("host API namespace must start with __marine_host_api_v");
+        }
+        byte_id += 1;
+    }
+
+    let mut version = 0;
+    while byte_id < namespace.len() {
+        let digit = namespace[byte_id];
+        if !digit.is_ascii_digit() {
+            panic!("host API version must be a decimal number");
+        }
+        version = version * 10 + (digit - b'0') as u32;
+        byte_id += 1;
+    }
+
+    version
+}
+
+#[cfg(test)]
+mod tests {
+    use super::host_api_version_section;
+    use super::version_from_namespace;
+    use super::HOST_API_NAMESPACE;
+    use super::HOST_API_NAMESPACE_PREFIX;
+    use super::HOST_API_VERSION;
+
+    #[test]
+    fn namespace_contains_version() {
+        assert_eq!(
+            HOST_API_NAMESPACE,
+            format!("{}{}", HOST_API_NAMESPACE_PREFIX, HOST_API_VERSION)
+        );
+        assert_eq!(version_from_namespace("__marine_host_api_v12"), 12);
+    }
+
+    #[test]
+    fn selected_version_is_used() {
+        let expected_version = if cfg!(feature = "host-api-v4") { 4 } else { 3 };
+
+        assert_eq!(HOST_API_VERSION, expected_version);
+    }
+
+    #[test]
+    fn version_section_layout() {
+        let expected_version = if cfg!(feature = "host-api-v4") { 4 } else { 3 };
+
+        assert_eq!(host_api_version_section(), [expected_version, 0, 0, 0]);
+    }
+}
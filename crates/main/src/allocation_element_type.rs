@@ -0,0 +1,211 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Types of elements of memory regions allocated by `allocate`.
+//!
+//! Host passes the type id to `allocate` and the generated code lifts the region as a `Vec`
+//! of the same elements, so size and alignment of each type are declared in the
+//! `__marine_allocation_element_types` custom section to let host check them.
+
+use std::convert::TryFrom;
+
+/// Type of elements of a memory region allocated by `allocate`, the discriminant is its id.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AllocationElementType {
+    Boolean = 0,
+    U8 = 1,
+    U16 = 2,
+    U32 = 3,
+    U64 = 4,
+    I8 = 5,
+    I16 = 6,
+    I32 = 7,
+    I64 = 8,
+    F32 = 9,
+    F64 = 10,
+    /// Pointers and sizes of strings, vectors and records, stored as u32.
+    /// Existing hosts never pass this id, they allocate such arrays as `U32`, which has the same
+    /// layout. It's used by the generated code to check arrays of pointers and is reserved
+    /// for hosts that distinguish them.
+    Pointer = 11,
+}
+
+impl AllocationElementType {
+    pub const ALL: [AllocationElementType; 12] = [
+        Self::Boolean,
+        Self::U8,
+        Self::U16,
+        Self::U32,
+        Self::U64,
+        Self::I8,
+        Self::I16,
+        Self::I32,
+        Self::I64,
+        Self::F32,
+        Self::F64,
+        Self::Pointer,
+    ];
+
+    pub const fn id(self) -> u8 {
+        self as u8
+    }
+
+    pub const fn from_id(id: u8) -> Option<Self> {
+        if (id as usize) < Self::ALL.len() {
+            Some(Self::ALL[id as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Size of an element in bytes.
+    pub const fn size(self) -> usize {
+        use std::mem::size_of;
+
+        match self {
+            // booleans are passed as bytes
+            Self::Boolean | Self::U8 => size_of::<u8>(),
+            Self::U16 => size_of::<u16>(),
+            Self::U32 | Self::Pointer => size_of::<u32>(),
+            Self::U64 => size_of::<u64>(),
+            Self::I8 => size_of::<i8>(),
+            Self::I16 => size_of::<i16>(),
+            Self::I32 => size_of::<i32>(),
+            Self::I64 => size_of::<i64>(),
+            Self::F32 => size_of::<f32>(),
+            Self::F64 => size_of::<f64>(),
+        }
+    }
+
+    /// Alignment of an element in bytes.
+    pub const fn align(self) -> usize {
+        use std::mem::align_of;
+
+        match self {
+            Self::Boolean | Self::U8 => align_of::<u8>(),
+            Self::U16 => align_of::<u16>(),
+            Self::U32 | Self::Pointer => align_of::<u32>(),
+            Self::U64 => align_of::<u64>(),
+            Self::I8 => align_of::<i8>(),
+            Self::I16 => align_of::<i16>(),
+            Self::I32 => align_of::<i32>(),
+            Self::I64 => align_of::<i64>(),
+            Self::F32 => align_of::<f32>(),
+            Self::F64 => align_of::<f64>(),
+        }
+    }
+}
+
+impl TryFrom<u8> for AllocationElementType {
+    type Error = u8;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        Self::from_id(id).ok_or(id)
+    }
+}
+
+// TODO: avoid duplication with the link_section when key-value attributes become stable
+pub const ALLOCATION_ELEMENT_TYPES_SECTION_NAME: &str = "__marine_allocation_element_types";
+
+// the table is used only by the link_section static, that isn't considered as a use
+#[allow(dead_code)]
+const ELEMENT_TYPES_TABLE_SIZE: usize = 3 * AllocationElementType::ALL.len();
+
+/// (id, size, align) of every element type.
+#[allow(dead_code)]
+const fn element_types_table() -> [u8; ELEMENT_TYPES_TABLE_SIZE] {
+    let mut table = [0; ELEMENT_TYPES_TABLE_SIZE];
+    let mut type_id = 0;
+    while type_id < AllocationElementType::ALL.len() {
+        let ty = AllocationElementType::ALL[type_id];
+        table[3 * type_id] = ty.id();
+        table[3 * type_id + 1] = ty.size() as u8;
+        table[3 * type_id + 2] = ty.align() as u8;
+        type_id += 1;
+    }
+
+    table
+}
+
+#[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
+#[link_section = "__marine_allocation_element_types"]
+#[doc(hidden)]
+pub static __M_ALLOCATION_ELEMENT_TYPES: [u8; ELEMENT_TYPES_TABLE_SIZE] = element_types_table();
+
+#[cfg(test)]
+mod tests {
+    use super::element_types_table;
+    use super::AllocationElementType;
+    use std::convert::TryFrom;
+    use std::mem::align_of;
+    use std::mem::size_of;
+
+    fn layout_of<T>() -> (usize, usize) {
+        (size_of::<T>(), align_of::<T>())
+    }
+
+    #[test]
+    fn ids_are_discriminants() {
+        for (id, ty) in AllocationElementType::ALL.iter().enumerate() {
+            assert_eq!(ty.id() as usize, id);
+            assert_eq!(AllocationElementType::try_from(id as u8), Ok(*ty));
+        }
+
+        assert_eq!(AllocationElementType::try_from(12), Err(12));
+        assert_eq!(AllocationElementType::from_id(u8::MAX), None);
+    }
+
+    #[test]
+    fn layouts_are_fixed() {
+        use AllocationElementType::*;
+
+        // host relies on these layouts, so they are pinned rather than derived from Rust types
+        let expected = [
+            (Boolean, (1, 1)),
+            (U8, (1, 1)),
+            (U16, (2, 2)),
+            (U32, (4, 4)),
+            (U64, (8, 8)),
+            (I8, (1, 1)),
+            (I16, (2, 2)),
+            (I32, (4, 4)),
+            (I64, (8, 8)),
+            (F32, (4, 4)),
+            (F64, (8, 8)),
+            (Pointer, (4, 4)),
+        ];
+        assert_eq!(expected.len(), AllocationElementType::ALL.len());
+
+        for (ty, layout) in expected {
+            assert_eq!((ty.size(), ty.align()), layout, "{:?}", ty);
+        }
+    }
+
+    #[test]
+    fn bool_is_passed_as_byte() {
+        // allocate creates a byte region for booleans, that is valid only for 0 and 1
+        assert_eq!(layout_of::<bool>(), layout_of::<u8>());
+    }
+
+    #[test]
+    fn table_describes_all_types() {
+        let table = element_types_table();
+        for (entry, ty) in table.chunks(3).zip(AllocationElementType::ALL) {
+            assert_eq!(entry, [ty.id(), ty.size() as u8, ty.align() as u8]);
+        }
+    }
+}
@@ -0,0 +1,436 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! An optional global allocator that serves small allocations from 64 KiB chunks
+//! by bumping a cursor.
+//!
+//! Most allocations of a module live only during one export call: lifted arguments,
+//! intermediate values and lowered results released by `release_objects`. Every chunk counts
+//! its live allocations, so it is reused as a whole when they all are freed, and spare chunks
+//! are returned to the system allocator at the end of each call. Allocations that outlive
+//! a call just keep their chunk alive, so the arena is safe for any allocation pattern.
+//! Large or overaligned allocations are passed to the system allocator.
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::ptr;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+/// Size and alignment of chunks requested from the system allocator.
+pub const ARENA_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Allocations larger than this are served by the system allocator.
+const LARGE_ALLOCATION_SIZE: usize = ARENA_CHUNK_SIZE / 4;
+
+/// Allocations with greater alignment are served by the system allocator.
+const MAX_ARENA_ALIGN: usize = 4096;
+
+/// Count of free chunks kept for the next call by `release_free_chunks`.
+const KEPT_FREE_CHUNKS: usize = 4;
+
+/// Usage statistics of an [`ArenaAllocator`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArenaStats {
+    /// Bytes of currently live allocations.
+    pub live_bytes: usize,
+
+    /// Maximum of live_bytes since the start of the module.
+    pub peak_bytes: usize,
+
+    /// Chunks currently requested from the system allocator, including the free ones.
+    pub chunks: usize,
+
+    /// Chunks without live allocations kept for reuse.
+    pub free_chunks: usize,
+
+    /// Currently live allocations served by the system allocator.
+    pub large_allocations: usize,
+
+    /// Count of ends of export calls, i.e. of `release_objects` calls.
+    pub calls: usize,
+}
+
+/// A chunk allocator, see the module documentation. Registered as a global allocator
+/// by the `arena-allocator` feature.
+pub struct ArenaAllocator {
+    state: Mutex<ArenaState>,
+}
+
+struct ArenaState {
+    current: *mut ChunkHeader,
+    free: *mut ChunkHeader,
+    stats: ArenaStats,
+}
+
+// Chunks are owned by the allocator and accessed only under the mutex.
+unsafe impl Send for ArenaState {}
+
+/// Placed at the start of each chunk.
+struct ChunkHeader {
+    next: *mut ChunkHeader,
+    live: usize,
+    cursor: usize,
+}
+
+const CHUNK_HEADER_SIZE: usize = std::mem::size_of::<ChunkHeader>();
+
+impl ArenaAllocator {
+    pub const fn new() -> Self {
+        let stats = ArenaStats {
+            live_bytes: 0,
+            peak_bytes: 0,
+            chunks: 0,
+            free_chunks: 0,
+            large_allocations: 0,
+            calls: 0,
+        };
+
+        Self {
+            state: Mutex::new(ArenaState {
+                current: ptr::null_mut(),
+                free: ptr::null_mut(),
+                stats,
+            }),
+        }
+    }
+
+    pub fn stats(&self) -> ArenaStats {
+        self.lock().stats
+    }
+
+    /// Returns free chunks except a few ones to the system allocator,
+    /// called at the end of each export call.
+    pub fn release_free_chunks(&self) {
+        let mut state = self.lock();
+        state.stats.calls += 1;
+
+        while state.stats.free_chunks > KEPT_FREE_CHUNKS {
+            let chunk = state.free;
+            // chunks in the free list are valid until deallocated here
+            unsafe {
+                state.free = (*chunk).next;
+                System.dealloc(chunk as *mut u8, chunk_layout());
+            }
+            state.stats.free_chunks -= 1;
+            state.stats.chunks -= 1;
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ArenaState> {
+        // state is kept consistent by every operation, so it's safe to use it after a panic
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for ArenaAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl GlobalAlloc for ArenaAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if is_large(&layout) {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                let mut state = self.lock();
+                state.stats.large_allocations += 1;
+                state.add_live_bytes(layout.size());
+            }
+            return ptr;
+        }
+
+        self.lock().alloc_small(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if is_large(&layout) {
+            System.dealloc(ptr, layout);
+            let mut state = self.lock();
+            state.stats.large_allocations -= 1;
+            state.stats.live_bytes -= layout.size();
+            return;
+        }
+
+        self.lock().dealloc_small(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        if !is_large(&layout) && !is_large(&new_layout) {
+            let mut state = self.lock();
+            if state.resize_last(ptr, layout.size(), new_size) {
+                return ptr;
+            }
+        }
+
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+            self.dealloc(ptr, layout);
+        }
+
+        new_ptr
+    }
+}
+
+impl ArenaState {
+    unsafe fn alloc_small(&mut self, layout: Layout) -> *mut u8 {
+        if !self.current.is_null() {
+            if let Some(ptr) = bump(self.current, &layout) {
+                self.add_live_bytes(layout.size());
+                return ptr;
+            }
+        }
+
+        let chunk = match self.take_free_chunk() {
+            Some(chunk) => chunk,
+            None => {
+                let chunk = System.alloc(chunk_layout()) as *mut ChunkHeader;
+                if chunk.is_null() {
+                    return ptr::null_mut();
+                }
+                self.stats.chunks += 1;
+                chunk
+            }
+        };
+
+        // the previous chunk has live allocations, otherwise the bump would succeed,
+        // it will be found by address and reused when they are freed
+        chunk.write(ChunkHeader {
+            next: ptr::null_mut(),
+            live: 0,
+            cursor: CHUNK_HEADER_SIZE,
+        });
+        self.current = chunk;
+
+        match bump(chunk, &layout) {
+            Some(ptr) => {
+                self.add_live_bytes(layout.size());
+                ptr
+            }
+            None => ptr::null_mut(),
+        }
+    }
+
+    unsafe fn dealloc_small(&mut self, ptr: *mut u8, layout: Layout) {
+        let chunk = chunk_of(ptr);
+        self.stats.live_bytes -= layout.size();
+        (*chunk).live -= 1;
+        if (*chunk).live != 0 {
+            return;
+        }
+
+        (*chunk).cursor = CHUNK_HEADER_SIZE;
+        if chunk != self.current {
+            (*chunk).next = self.free;
+            self.free = chunk;
+            self.stats.free_chunks += 1;
+        }
+    }
+
+    /// Resizes the last allocation of the current chunk in place, if it fits.
+    unsafe fn resize_last(&mut self, ptr: *mut u8, old_size: usize, new_size: usize) -> bool {
+        let chunk = chunk_of(ptr);
+        if chunk != self.current || ptr as usize + old_size != chunk as usize + (*chunk).cursor {
+            return false;
+        }
+
+        let new_cursor = ptr as usize - chunk as usize + new_size;
+        if new_cursor > ARENA_CHUNK_SIZE {
+            return false;
+        }
+
+        (*chunk).cursor = new_cursor;
+        self.stats.live_bytes -= old_size;
+        self.add_live_bytes(new_size);
+        true
+    }
+
+    unsafe fn take_free_chunk(&mut self) -> Option<*mut ChunkHeader> {
+        if self.free.is_null() {
+            return None;
+        }
+
+        let chunk = self.free;
+        self.free = (*chunk).next;
+        self.stats.free_chunks -= 1;
+        Some(chunk)
+    }
+
+    fn add_live_bytes(&mut self, size: usize) {
+        self.stats.live_bytes += size;
+        self.stats.peak_bytes = self.stats.peak_bytes.max(self.stats.live_bytes);
+    }
+}
+
+unsafe fn bump(chunk: *mut ChunkHeader, layout: &Layout) -> Option<*mut u8> {
+    let chunk_start = chunk as usize;
+    let start = align_up(chunk_start + (*chunk).cursor, layout.align());
+    let end = start + layout.size();
+    if end > chunk_start + ARENA_CHUNK_SIZE {
+        return None;
+    }
+
+    (*chunk).cursor = end - chunk_start;
+    (*chunk).live += 1;
+    Some(start as *mut u8)
+}
+
+fn chunk_of(ptr: *mut u8) -> *mut ChunkHeader {
+    (ptr as usize & !(ARENA_CHUNK_SIZE - 1)) as *mut ChunkHeader
+}
+
+fn chunk_layout() -> Layout {
+    // size is a power of two, so the layout is valid
+    unsafe { Layout::from_size_align_unchecked(ARENA_CHUNK_SIZE, ARENA_CHUNK_SIZE) }
+}
+
+fn is_large(layout: &Layout) -> bool {
+    layout.size() > LARGE_ALLOCATION_SIZE || layout.align() > MAX_ARENA_ALIGN
+}
+
+fn align_up(addr: usize, align: usize) -> usize {
+    (addr + align - 1) & !(align - 1)
+}
+
+// with the memory-stats feature the arena is wrapped by the tracking allocator
+#[cfg(all(
+    feature = "marine-abi",
+    target_arch = "wasm32",
+    not(feature = "memory-stats")
+))]
+#[global_allocator]
+static ALLOCATOR: ArenaAllocator = ArenaAllocator::new();
+
+#[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
+fn global_arena() -> &'static ArenaAllocator {
+    #[cfg(not(feature = "memory-stats"))]
+    return &ALLOCATOR;
+
+    #[cfg(feature = "memory-stats")]
+    return crate::tracking_allocator::global_allocator().inner();
+}
+
+/// Returns usage statistics of the global arena allocator.
+#[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
+pub fn arena_stats() -> ArenaStats {
+    global_arena().stats()
+}
+
+#[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
+pub(crate) fn release_free_chunks() {
+    global_arena().release_free_chunks()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArenaAllocator;
+    use super::ARENA_CHUNK_SIZE;
+    use super::KEPT_FREE_CHUNKS;
+    use super::LARGE_ALLOCATION_SIZE;
+    use std::alloc::GlobalAlloc;
+    use std::alloc::Layout;
+
+    #[test]
+    fn chunks_are_reused_when_allocations_are_freed() {
+        let arena = ArenaAllocator::new();
+        let layout = Layout::from_size_align(1000, 8).unwrap();
+
+        unsafe {
+            let allocations: Vec<_> = (0..200).map(|_| arena.alloc(layout)).collect();
+            assert!(allocations.iter().all(|ptr| *ptr as usize % 8 == 0));
+
+            let stats = arena.stats();
+            assert_eq!(stats.live_bytes, 200 * 1000);
+            assert_eq!(stats.chunks, 4);
+
+            for ptr in allocations {
+                arena.dealloc(ptr, layout);
+            }
+
+            let stats = arena.stats();
+            assert_eq!(stats.live_bytes, 0);
+            assert_eq!(stats.peak_bytes, 200 * 1000);
+            assert_eq!(stats.free_chunks, 3);
+
+            let ptr = arena.alloc(layout);
+            arena.dealloc(ptr, layout);
+            assert_eq!(arena.stats().chunks, 4);
+        }
+    }
+
+    #[test]
+    fn free_chunks_are_released_at_the_end_of_call() {
+        let arena = ArenaAllocator::new();
+        let layout = Layout::from_size_align(LARGE_ALLOCATION_SIZE, 1).unwrap();
+
+        unsafe {
+            let allocations: Vec<_> = (0..40).map(|_| arena.alloc(layout)).collect();
+            for ptr in allocations {
+                arena.dealloc(ptr, layout);
+            }
+
+            arena.release_free_chunks();
+            let stats = arena.stats();
+            assert_eq!(stats.free_chunks, KEPT_FREE_CHUNKS);
+            assert_eq!(stats.chunks, KEPT_FREE_CHUNKS + 1);
+            assert_eq!(stats.calls, 1);
+        }
+    }
+
+    #[test]
+    fn last_allocation_grows_in_place() {
+        let arena = ArenaAllocator::new();
+        let layout = Layout::from_size_align(16, 4).unwrap();
+
+        unsafe {
+            let first = arena.alloc(layout);
+            let last = arena.alloc(layout);
+            last.write_bytes(7, 16);
+
+            assert_eq!(arena.realloc(last, layout, 256), last);
+            assert_eq!(*last.add(15), 7);
+
+            let moved = arena.realloc(first, layout, 256);
+            assert_ne!(moved, first);
+            assert_eq!(arena.stats().live_bytes, 512);
+
+            arena.dealloc(last, Layout::from_size_align(256, 4).unwrap());
+            arena.dealloc(moved, Layout::from_size_align(256, 4).unwrap());
+            assert_eq!(arena.stats().live_bytes, 0);
+        }
+    }
+
+    #[test]
+    fn large_allocations_use_system_allocator() {
+        let arena = ArenaAllocator::new();
+        let layout = Layout::from_size_align(ARENA_CHUNK_SIZE, 8).unwrap();
+
+        unsafe {
+            let ptr = arena.alloc(layout);
+            assert_eq!(arena.stats().large_allocations, 1);
+            assert_eq!(arena.stats().chunks, 0);
+
+            arena.dealloc(ptr, layout);
+            assert_eq!(arena.stats().large_allocations, 0);
+        }
+    }
+}
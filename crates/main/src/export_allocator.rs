@@ -14,17 +14,43 @@
  * limitations under the License.
  */
 
+use crate::AllocationElementType;
+
+use std::convert::TryFrom;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+
+/// The last `allocate` call succeeded.
+pub const ALLOCATION_SUCCESS: u32 = 0;
+
+/// The last `allocate` call failed because the module is out of memory.
+pub const ALLOCATION_OUT_OF_MEMORY: u32 = 1;
+
+/// The last `allocate` call failed because of an unknown element type.
+pub const ALLOCATION_UNKNOWN_TYPE: u32 = 2;
+
+static ALLOCATION_ERROR: AtomicU32 = AtomicU32::new(ALLOCATION_SUCCESS);
+
 /// Allocates memory area of specified size and type and returns its address.
 /// The allocated memory region is intended to be use as a Vec.
+/// Returns 0 if memory couldn't be allocated, the reason is returned by `get_allocation_error`.
 #[no_mangle]
 pub unsafe fn allocate(elem_count: usize, elem_ty: usize) -> usize {
+    ALLOCATION_ERROR.store(ALLOCATION_SUCCESS, Ordering::Relaxed);
+
     if elem_count == 0 {
         // otherwise 1 would be returned due to the internals of Vec in Rust
         return 0;
     }
 
     #[allow(clippy::let_and_return)]
-    let allocated_mem = allocate_impl(elem_count, elem_ty);
+    let allocated_mem = match allocate_impl(elem_count, elem_ty) {
+        Ok(allocated_mem) => allocated_mem,
+        Err(error_code) => {
+            ALLOCATION_ERROR.store(error_code, Ordering::Relaxed);
+            0
+        }
+    };
     crate::debug_log!(format!(
         "sdk.allocate: {} {} -> {}\n",
         elem_count, elem_ty, allocated_mem
@@ -33,29 +59,44 @@ pub unsafe fn allocate(elem_count: usize, elem_ty: usize) -> usize {
     allocated_mem
 }
 
-fn allocate_impl(elem_count: usize, elem_ty: usize) -> usize {
+/// Returns the error code of the last `allocate` call, a host could call it after `allocate`
+/// returned 0 to find out the reason.
+#[no_mangle]
+pub fn get_allocation_error() -> u32 {
+    ALLOCATION_ERROR.load(Ordering::Relaxed)
+}
+
+fn allocate_impl(elem_count: usize, elem_ty: usize) -> Result<usize, u32> {
+    use AllocationElementType::*;
+
+    let elem_ty = u8::try_from(elem_ty)
+        .ok()
+        .and_then(AllocationElementType::from_id)
+        .ok_or(ALLOCATION_UNKNOWN_TYPE)?;
+
     match elem_ty {
-        0 => allocate_vec::<u8>(elem_count), // for booleans
-        1 => allocate_vec::<u8>(elem_count),
-        2 => allocate_vec::<u16>(elem_count),
-        3 => allocate_vec::<u32>(elem_count),
-        4 => allocate_vec::<u64>(elem_count),
-        5 => allocate_vec::<i8>(elem_count),
-        6 => allocate_vec::<i16>(elem_count),
-        7 => allocate_vec::<i32>(elem_count),
-        8 => allocate_vec::<i64>(elem_count),
-        9 => allocate_vec::<f32>(elem_count),
-        10 => allocate_vec::<f64>(elem_count),
-        _ => 0,
+        Boolean => allocate_vec::<u8>(elem_count),
+        U8 => allocate_vec::<u8>(elem_count),
+        U16 => allocate_vec::<u16>(elem_count),
+        U32 => allocate_vec::<u32>(elem_count),
+        U64 => allocate_vec::<u64>(elem_count),
+        I8 => allocate_vec::<i8>(elem_count),
+        I16 => allocate_vec::<i16>(elem_count),
+        I32 => allocate_vec::<i32>(elem_count),
+        I64 => allocate_vec::<i64>(elem_count),
+        F32 => allocate_vec::<f32>(elem_count),
+        F64 => allocate_vec::<f64>(elem_count),
+        Pointer => allocate_vec::<u32>(elem_count),
     }
 }
 
-fn allocate_vec<T>(count: usize) -> usize {
-    // TODO: handle OOM
+fn allocate_vec<T>(count: usize) -> Result<usize, u32> {
     // This allocation scheme with vectors is needed to deal with internal Vec layout
-    let vec = Vec::<T>::with_capacity(count);
+    let mut vec = Vec::<T>::new();
+    vec.try_reserve_exact(count)
+        .map_err(|_| ALLOCATION_OUT_OF_MEMORY)?;
     let offset = vec.as_ptr() as usize;
     std::mem::forget(vec);
 
-    offset
+    Ok(offset)
 }
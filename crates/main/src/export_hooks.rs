@@ -0,0 +1,43 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Hooks called by the code generated by `#[marine]` around each export function call.
+
+use std::cell::Cell;
+
+thread_local! {
+    static CURRENT_EXPORT: Cell<Option<&'static str>> = Cell::new(None);
+}
+
+/// Called before arguments of an export function are converted from Wasm types.
+pub fn enter_export(export_name: &'static str) {
+    CURRENT_EXPORT.with(|current| current.set(Some(export_name)));
+}
+
+/// Called after the original export function has returned, but before its result
+/// is passed to the host.
+pub fn exit_export() {
+    #[cfg(feature = "logger")]
+    crate::logger::flush_logger();
+
+    CURRENT_EXPORT.with(|current| current.set(None));
+}
+
+/// Returns the name of the export function which is being called now, if any.
+#[cfg(feature = "logger")]
+pub(crate) fn current_export() -> Option<&'static str> {
+    CURRENT_EXPORT.with(Cell::get)
+}
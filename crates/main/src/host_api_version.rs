@@ -0,0 +1,34 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Declares the host API version required by a module, so that a host could reject
+//! an incompatible module at load time instead of failing on a missing import.
+
+#[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
+#[link_section = "__marine_host_api_version"]
+#[doc(hidden)]
+pub static __M_HOST_API_VERSION: [u8; 4] = marine_host_api::host_api_version_section();
+
+/// Returns the host API version required by this module.
+#[cfg(all(
+    feature = "host-api-version-export",
+    feature = "marine-abi",
+    target_arch = "wasm32"
+))]
+#[no_mangle]
+pub fn __marine_host_api_version() -> u32 {
+    marine_host_api::HOST_API_VERSION
+}
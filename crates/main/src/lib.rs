@@ -14,8 +14,8 @@
  * limitations under the License.
  */
 
-//! The main part of Fluence backend SDK. Contains `export_allocator`, `logger` and `result`
-//! modules.
+//! The main part of Fluence backend SDK. Contains `export_allocator`, `arena_allocator`,
+//! `tracking_allocator`, `host_api_version`, `logger`, `tracing_layer` and `result` modules.
 
 #![allow(clippy::missing_safety_doc)]
 #![allow(clippy::needless_doctest_main)]
@@ -31,27 +31,68 @@
 )]
 #![warn(rust_2018_idioms)]
 
+mod allocation_element_type;
+#[cfg(feature = "arena-allocator")]
+mod arena_allocator;
 #[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
 mod export_allocator;
+mod export_hooks;
+mod host_api_version;
 #[cfg(any(feature = "debug", feature = "logger"))]
 mod logger;
 mod module_manifest;
 #[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
 mod result;
 mod sdk_version_embedder;
+#[cfg(feature = "memory-stats")]
+mod tracking_allocator;
+#[cfg(feature = "tracing")]
+mod tracing_layer;
 
 #[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
-pub use export_allocator::allocate;
+pub use export_allocator::{
+    allocate, get_allocation_error, ALLOCATION_SUCCESS, ALLOCATION_OUT_OF_MEMORY,
+    ALLOCATION_UNKNOWN_TYPE,
+};
+pub use export_hooks::{enter_export, exit_export};
+pub use allocation_element_type::{AllocationElementType, ALLOCATION_ELEMENT_TYPES_SECTION_NAME};
+
+#[cfg(feature = "arena-allocator")]
+pub use arena_allocator::{ArenaAllocator, ArenaStats, ARENA_CHUNK_SIZE};
+#[cfg(all(
+    feature = "arena-allocator",
+    feature = "marine-abi",
+    target_arch = "wasm32"
+))]
+pub use arena_allocator::arena_stats;
+
+#[cfg(feature = "memory-stats")]
+pub use tracking_allocator::{TrackingAllocator, AllocationStats};
+#[cfg(all(
+    feature = "memory-stats",
+    feature = "marine-abi",
+    target_arch = "wasm32"
+))]
+pub use tracking_allocator::allocation_stats;
 
 #[cfg(feature = "logger")]
-pub use logger::{WasmLoggerBuilder, TargetMap, WASM_LOG_ENV_NAME};
+pub use logger::{
+    WasmLoggerBuilder, TargetMap, WASM_LOG_ENV_NAME, TRUNCATION_MARKER, set_log_level, flush_logger,
+};
+
+#[cfg(feature = "tracing")]
+pub use tracing_layer::WasmTracingLayer;
 
 #[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
 pub use result::{
     get_result_ptr, get_result_size, set_result_ptr, set_result_size, release_objects,
-    add_object_to_release,
+    add_object_to_release, objects_to_release_count,
 };
 
+pub use marine_host_api::{
+    host_api_imports, HOST_API_NAMESPACE, HOST_API_VERSION, HOST_API_VERSION_SECTION_NAME,
+    HOST_API_VERSION_EXPORT_NAME,
+};
 pub use module_manifest::MANIFEST_SECTION_NAME;
 pub use sdk_version_embedder::VERSION_SECTION_NAME;
 
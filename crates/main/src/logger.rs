@@ -42,8 +42,23 @@
 //! [`WasmLogger`]: struct.WasmLogger.html
 //! [`log`]: https://docs.rs/log
 
+mod log_buffer;
+mod panic_hook;
+mod rate_limiter;
+
+use log_buffer::LogBuffer;
+use log_buffer::LogRecord;
+use rate_limiter::RateLimiter;
+
 use log::LevelFilter;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::time::Duration;
+use std::time::Instant;
 
 /// By default, logger will be initialized with log level from this environment variable.
 pub const WASM_LOG_ENV_NAME: &str = "WASM_LOG";
@@ -51,6 +66,12 @@ pub const WASM_LOG_ENV_NAME: &str = "WASM_LOG";
 /// If WASM_LOG_ENV isn't set, then this level will be used as the default.
 const WASM_DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::Info;
 
+/// This marker is appended to messages truncated to the maximum message length.
+pub const TRUNCATION_MARKER: &str = "...<truncated>";
+
+/// Messages dropped by the rate limiter are reported not more often than this interval by default.
+const DEFAULT_DROPPED_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
 /// Mapping from logging namespace string to its bitmask.
 /// TODO: use i64 for bitmask when wasmpack/bindgen issue with i64 is fixed.
 ///       Currently, i64 doesn't work on some versions of V8 because log_utf8_string function
@@ -58,7 +79,7 @@ const WASM_DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::Info;
 pub type TargetMap = HashMap<&'static str, i32>;
 
 /// This structure is used to save information about particular log level for a particular module.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 struct LogDirective {
     module_name: String,
     level: LevelFilter,
@@ -70,6 +91,19 @@ impl LogDirective {
     }
 }
 
+/// Log levels that could be adjusted at runtime after a logger is built.
+#[derive(Debug)]
+struct LogLevels {
+    modules_directives: Vec<LogDirective>,
+    default_log_level: LevelFilter,
+}
+
+/// Log levels of the built logger, they are adjusted by [`set_log_level`].
+static RUNTIME_LOG_LEVELS: OnceLock<Arc<RwLock<LogLevels>>> = OnceLock::new();
+
+/// Buffer of the built logger, it's flushed by [`flush_logger`].
+static RUNTIME_LOG_BUFFER: OnceLock<Arc<Mutex<LogBuffer>>> = OnceLock::new();
+
 /// The Wasm Logger.
 ///
 /// This struct implements the [`Log`] trait from the [`log`] crate, which allows it to act as a
@@ -80,64 +114,124 @@ impl LogDirective {
 ///
 /// [log-crate-url]: https://docs.rs/log/
 /// [`Log`]: https://docs.rs/log/0.4.11/log/trait.Log.html
-struct WasmLogger {
+pub(crate) struct WasmLogger {
     target_map: TargetMap,
-    modules_directives: Vec<LogDirective>,
-    default_log_level: LevelFilter,
+    log_levels: Arc<RwLock<LogLevels>>,
+    max_message_length: Option<usize>,
+    rate_limiter: Option<Mutex<RateLimiter>>,
+    buffer: Option<Arc<Mutex<LogBuffer>>>,
 }
 
 /// The Wasm logger builder.
 ///
 /// Build logger for the Fluence network, allows specifying target map and log level while building.
 pub struct WasmLoggerBuilder {
-    wasm_logger: WasmLogger,
+    target_map: TargetMap,
+    log_levels: LogLevels,
+    max_message_length: Option<usize>,
+    rate_limit: Option<(u32, u32)>,
+    dropped_report_interval: Duration,
+    buffer_capacity: Option<usize>,
+    panic_hook: bool,
 }
 
 impl WasmLoggerBuilder {
-    /// Initializes a builder of the global logger. Set log level based on the WASM_LOG environment variable if it set,
-    /// or [[WASM_DEFAULT_LOG_LEVEL]] otherwise. It is an initial method in this builder chain, please note,
-    /// that logger wouldn't work without subsequent build() call.
+    /// Initializes a builder of the global logger. Set log level and per module directives based on
+    /// the WASM_LOG environment variable if it set, or [[WASM_DEFAULT_LOG_LEVEL]] otherwise.
+    /// WASM_LOG follows the env_logger format, e.g. `info,my_crate::db=trace,noisy=off`.
+    /// It is an initial method in this builder chain, please note, that logger wouldn't work
+    /// without subsequent build() call.
     pub fn new() -> Self {
-        use std::str::FromStr;
-
-        let default_log_level = std::env::var(WASM_LOG_ENV_NAME)
-            .map_or(WASM_DEFAULT_LOG_LEVEL, |log_level_str| {
-                LevelFilter::from_str(&log_level_str).unwrap_or(WASM_DEFAULT_LOG_LEVEL)
-            });
+        let (default_log_level, modules_directives) = match std::env::var(WASM_LOG_ENV_NAME) {
+            Ok(spec) => {
+                let (default_log_level, modules_directives) = parse_log_directives(&spec);
+                let default_log_level = default_log_level.unwrap_or(WASM_DEFAULT_LOG_LEVEL);
+                (default_log_level, modules_directives)
+            }
+            Err(_) => (WASM_DEFAULT_LOG_LEVEL, Vec::new()),
+        };
 
-        let wasm_logger = WasmLogger {
-            target_map: HashMap::new(),
-            modules_directives: Vec::new(),
+        let log_levels = LogLevels {
+            modules_directives,
             default_log_level,
         };
 
-        Self { wasm_logger }
+        Self {
+            target_map: HashMap::new(),
+            log_levels,
+            max_message_length: None,
+            rate_limit: None,
+            dropped_report_interval: DEFAULT_DROPPED_REPORT_INTERVAL,
+            buffer_capacity: None,
+            panic_hook: false,
+        }
     }
 
     /// Set the log level.
     pub fn with_log_level(mut self, level: LevelFilter) -> Self {
-        self.wasm_logger.default_log_level = level;
+        self.log_levels.default_log_level = level;
         self
     }
 
     /// Set mapping between logging targets and numbers.
     /// Used to efficiently enable & disable logs per target on the host.
     pub fn with_target_map(mut self, map: TargetMap) -> Self {
-        self.wasm_logger.target_map = map;
+        self.target_map = map;
         self
     }
 
+    /// Set the maximum length of a message in bytes, longer messages are truncated
+    /// and marked with [`TRUNCATION_MARKER`].
+    pub fn with_max_message_length(mut self, max_message_length: usize) -> Self {
+        self.max_message_length = Some(max_message_length);
+        self
+    }
+
+    /// Limit the number of messages per logging target with a token bucket, which allows
+    /// `burst` messages at once and refills with `messages_per_second` rate.
+    /// Dropped messages are counted and periodically reported with a single warning.
+    pub fn with_rate_limit(mut self, burst: u32, messages_per_second: u32) -> Self {
+        self.rate_limit = Some((burst, messages_per_second));
+        self
+    }
+
+    /// Set how often the number of messages dropped by the rate limiter is reported.
+    pub fn with_dropped_report_interval(mut self, interval: Duration) -> Self {
+        self.dropped_report_interval = interval;
+        self
+    }
+
+    /// Accumulate messages in the module memory instead of passing each of them to the host
+    /// immediately. Buffered messages are flushed at the end of each export function call
+    /// or when their size exceeds `capacity` bytes. With `host-api-v4` they are flushed by one
+    /// host call, otherwise each of them is passed separately.
+    pub fn with_buffering(mut self, capacity: usize) -> Self {
+        self.buffer_capacity = Some(capacity);
+        self
+    }
+
+    /// Install a panic hook that reports panics to the host with the error level before trapping.
+    /// The report contains the panic message, its location and the name of the export function
+    /// being called. The previously installed panic hook is called after that.
+    pub fn with_panic_hook(mut self) -> Self {
+        self.panic_hook = true;
+        self
+    }
+
+    /// Set the log level for a particular module. Directives from the WASM_LOG environment
+    /// variable take precedence over the ones set here for the same module.
     pub fn filter(mut self, module_name: impl Into<String>, level: LevelFilter) -> Self {
         let module_name = module_name.into();
         let log_directive = LogDirective::new(module_name, level);
 
-        self.wasm_logger.modules_directives.push(log_directive);
+        self.log_levels.modules_directives.push(log_directive);
         self
     }
 
     /// Build the real logger.
     ///
     /// This method is a last one in this builder chain and MUST be called to set logger up.
+    /// Log levels of the built logger could be adjusted later by [`set_log_level`].
     /// Returns a error
     ///
     /// ```ignore
@@ -152,20 +246,80 @@ impl WasmLoggerBuilder {
     ///         .unwrap();
     /// # }
     /// ```
-    pub fn build(mut self) -> Result<(), log::SetLoggerError> {
-        let max_level = self.max_log_level();
-        self.sort_directives();
-
-        let Self { wasm_logger } = self;
+    pub fn build(self) -> Result<(), log::SetLoggerError> {
+        let max_level = self.log_levels.max_log_level();
+        let wasm_logger = self.into_logger();
 
+        let log_levels = wasm_logger.log_levels.clone();
+        let buffer = wasm_logger.buffer.clone();
         log::set_boxed_logger(Box::new(wasm_logger))?;
         log::set_max_level(max_level);
+
+        let _ = RUNTIME_LOG_LEVELS.set(log_levels);
+        if let Some(buffer) = buffer {
+            let _ = RUNTIME_LOG_BUFFER.set(buffer);
+        }
         Ok(())
     }
 
+    /// Build a [`tracing`] layer instead of the [`log`] logger.
+    ///
+    /// The layer shares log level directives and the target map with the logger, it could be
+    /// installed as the global subscriber with [`WasmTracingLayer::init`]. Log levels of the layer
+    /// could be adjusted later by [`set_log_level`], unless a logger was built before.
+    ///
+    /// [`tracing`]: https://docs.rs/tracing
+    /// [`log`]: https://docs.rs/log
+    /// [`WasmTracingLayer::init`]: crate::WasmTracingLayer::init
+    #[cfg(feature = "tracing")]
+    pub fn build_tracing_layer(self) -> crate::WasmTracingLayer {
+        let wasm_logger = self.into_logger();
+        let _ = RUNTIME_LOG_LEVELS.set(wasm_logger.log_levels.clone());
+        if let Some(buffer) = &wasm_logger.buffer {
+            let _ = RUNTIME_LOG_BUFFER.set(buffer.clone());
+        }
+
+        crate::WasmTracingLayer::new(wasm_logger)
+    }
+
+    fn into_logger(self) -> WasmLogger {
+        let Self {
+            target_map,
+            mut log_levels,
+            max_message_length,
+            rate_limit,
+            dropped_report_interval,
+            buffer_capacity,
+            panic_hook,
+        } = self;
+        log_levels.sort_directives();
+
+        let mut wasm_logger = WasmLogger::new(target_map, log_levels);
+        wasm_logger.max_message_length = max_message_length;
+        wasm_logger.rate_limiter = rate_limit.map(|(burst, messages_per_second)| {
+            let rate_limiter = RateLimiter::new(
+                burst,
+                messages_per_second,
+                dropped_report_interval,
+                Instant::now(),
+            );
+            Mutex::new(rate_limiter)
+        });
+        wasm_logger.buffer =
+            buffer_capacity.map(|capacity| Arc::new(Mutex::new(LogBuffer::new(capacity))));
+
+        if panic_hook {
+            panic_hook::install();
+        }
+
+        wasm_logger
+    }
+}
+
+impl LogLevels {
     /// Sort supplied directive ny length of module names to make more efficient lookup at runtime.
     fn sort_directives(&mut self) {
-        self.wasm_logger.modules_directives.sort_by(|l, r| {
+        self.modules_directives.sort_by(|l, r| {
             let llen = l.module_name.len();
             let rlen = r.module_name.len();
 
@@ -174,9 +328,8 @@ impl WasmLoggerBuilder {
     }
 
     fn max_log_level(&self) -> log::LevelFilter {
-        let default_level = self.wasm_logger.default_log_level;
+        let default_level = self.default_log_level;
         let max_filter_level = self
-            .wasm_logger
             .modules_directives
             .iter()
             .map(|d| d.level)
@@ -185,10 +338,7 @@ impl WasmLoggerBuilder {
 
         std::cmp::max(default_level, max_filter_level)
     }
-}
 
-impl log::Log for WasmLogger {
-    #[inline]
     fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
         let target = metadata.target();
 
@@ -201,9 +351,208 @@ impl log::Log for WasmLogger {
         metadata.level() <= self.default_log_level
     }
 
+    fn set_level(&mut self, target: Option<&str>, level: LevelFilter) {
+        let module_name = match target {
+            Some(module_name) => module_name,
+            None => {
+                self.default_log_level = level;
+                return;
+            }
+        };
+
+        match self
+            .modules_directives
+            .iter_mut()
+            .find(|d| d.module_name == module_name)
+        {
+            Some(directive) => directive.level = level,
+            None => {
+                let directive = LogDirective::new(module_name.to_string(), level);
+                self.modules_directives.push(directive);
+                self.sort_directives();
+            }
+        }
+    }
+}
+
+impl WasmLogger {
+    fn new(target_map: TargetMap, log_levels: LogLevels) -> Self {
+        Self {
+            target_map,
+            log_levels: Arc::new(RwLock::new(log_levels)),
+            max_message_length: None,
+            rate_limiter: None,
+            buffer: None,
+        }
+    }
+
+    /// Returns false if the message should be dropped due to the rate limit,
+    /// also reports previously dropped messages when it's time to.
+    fn check_rate_limit(&self, target: &str) -> bool {
+        let rate_limiter = match &self.rate_limiter {
+            Some(rate_limiter) => rate_limiter,
+            None => return true,
+        };
+
+        let now = Instant::now();
+        let mut rate_limiter = rate_limiter.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(dropped_count) = rate_limiter.take_dropped_report(now) {
+            let msg = format!(
+                "{} log messages were dropped due to the rate limit",
+                dropped_count
+            );
+            self.write(log::Level::Warn as i32, 0, &msg);
+        }
+
+        rate_limiter.try_acquire(target, now)
+    }
+
+    /// Passes a message to the host or appends it to the buffer in the buffered mode.
+    fn write(&self, level: i32, target: i32, msg: &str) {
+        let buffer = match &self.buffer {
+            Some(buffer) => buffer,
+            None => return write_log(level, target, msg),
+        };
+
+        let mut buffer = buffer.lock().unwrap_or_else(PoisonError::into_inner);
+        if buffer.push(level, target, msg) {
+            flush_buffer(&mut buffer);
+        }
+    }
+}
+
+/// Flushes messages accumulated by the built logger in the buffered mode.
+/// It's called by the generated code at the end of each export function.
+pub fn flush_logger() {
+    if let Some(buffer) = RUNTIME_LOG_BUFFER.get() {
+        flush_buffer(&mut buffer.lock().unwrap_or_else(PoisonError::into_inner));
+    }
+}
+
+fn flush_buffer(buffer: &mut LogBuffer) {
+    let records = buffer.take_records();
+    if !records.is_empty() {
+        write_log_batch(&records);
+    }
+}
+
+fn truncate_message(mut msg: String, max_message_length: usize) -> String {
+    if msg.len() <= max_message_length {
+        return msg;
+    }
+
+    let mut boundary = max_message_length;
+    while !msg.is_char_boundary(boundary) {
+        boundary -= 1;
+    }
+    msg.truncate(boundary);
+    msg.push_str(TRUNCATION_MARKER);
+
+    msg
+}
+
+fn write_log(level: i32, target: i32, msg: &str) {
+    log_utf8_string(level, target, msg.as_ptr() as _, msg.len() as _);
+}
+
+/// Passes all buffered records to the host in one call, hosts of the v3 accept
+/// only separate messages.
+#[cfg(all(
+    feature = "marine-abi",
+    target_arch = "wasm32",
+    feature = "host-api-v4"
+))]
+fn write_log_batch(records: &[LogRecord]) {
+    let batch = log_buffer::encode_batch(records);
+    unsafe { log_utf8_batch_impl(batch.as_ptr() as _, batch.len() as _) };
+}
+
+#[cfg(not(all(
+    feature = "marine-abi",
+    target_arch = "wasm32",
+    feature = "host-api-v4"
+)))]
+fn write_log_batch(records: &[LogRecord]) {
+    for record in records {
+        write_log(record.level, record.target, &record.msg);
+    }
+}
+
+/// Adjusts log levels of the built logger at runtime. If target is None, the default log level
+/// is set, otherwise a directive for this target is added or replaced.
+/// Returns false if a logger hasn't been built yet.
+pub fn set_log_level(target: Option<&str>, level: LevelFilter) -> bool {
+    match RUNTIME_LOG_LEVELS.get() {
+        Some(log_levels) => {
+            update_log_level(log_levels, target, level);
+            true
+        }
+        None => false,
+    }
+}
+
+fn update_log_level(log_levels: &RwLock<LogLevels>, target: Option<&str>, level: LevelFilter) {
+    {
+        let mut log_levels = log_levels.write().unwrap_or_else(PoisonError::into_inner);
+        log_levels.set_level(target, level);
+        log::set_max_level(log_levels.max_log_level());
+    }
+
+    // tracing caches interest of a callsite after its first use, so it should be recalculated
+    #[cfg(feature = "tracing")]
+    tracing_core::callsite::rebuild_interest_cache();
+}
+
+/// Parses log directives in the env_logger format: a comma-separated list of either
+/// `module_name=level`, a bare `level` which sets the default log level, or a bare `module_name`
+/// which enables all logs for this module. Invalid directives are ignored.
+fn parse_log_directives(spec: &str) -> (Option<LevelFilter>, Vec<LogDirective>) {
+    use std::str::FromStr;
+
+    let mut default_log_level = None;
+    let mut modules_directives = Vec::new();
+
+    for directive in spec.split(',').map(str::trim) {
+        if directive.is_empty() {
+            continue;
+        }
+
+        match directive.split_once('=') {
+            Some((module_name, level)) => {
+                let module_name = module_name.trim();
+                let level = match LevelFilter::from_str(level.trim()) {
+                    Ok(level) => level,
+                    Err(_) => continue,
+                };
+
+                if module_name.is_empty() {
+                    continue;
+                }
+                modules_directives.push(LogDirective::new(module_name.to_string(), level));
+            }
+            None => match LevelFilter::from_str(directive) {
+                Ok(level) => default_log_level = Some(level),
+                Err(_) => modules_directives
+                    .push(LogDirective::new(directive.to_string(), LevelFilter::Trace)),
+            },
+        }
+    }
+
+    (default_log_level, modules_directives)
+}
+
+impl log::Log for WasmLogger {
+    #[inline]
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        self.log_levels
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .enabled(metadata)
+    }
+
     #[inline]
     fn log(&self, record: &log::Record<'_>) {
-        if !self.enabled(record.metadata()) {
+        if !self.enabled(record.metadata()) || !self.check_rate_limit(record.metadata().target()) {
             return;
         }
 
@@ -214,13 +563,21 @@ impl log::Log for WasmLogger {
             .get(record.metadata().target())
             .unwrap_or(&default_target);
         let msg = record.args().to_string();
+        let msg = match self.max_message_length {
+            Some(max_message_length) => truncate_message(msg, max_message_length),
+            None => msg,
+        };
 
-        log_utf8_string(level, target, msg.as_ptr() as _, msg.len() as _);
+        self.write(level, target, &msg);
     }
 
-    // in our case flushing is performed by a host itself
+    // in the unbuffered mode flushing is performed by a host itself
     #[inline]
-    fn flush(&self) {}
+    fn flush(&self) {
+        if let Some(buffer) = &self.buffer {
+            flush_buffer(&mut buffer.lock().unwrap_or_else(PoisonError::into_inner));
+        }
+    }
 }
 
 #[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
@@ -238,15 +595,28 @@ pub fn log_utf8_string(level: i32, target: i32, msg_ptr: i32, msg_size: i32) {
     println!("[{}] {} {}", level, target, msg);
 }
 
-#[allow(unused_doc_comments)]
-/// TODO: mark `log_utf8_string_impl` as #[wasm_bindgen], so it is polyfilled by bindgen
-/// log_utf8_string should be provided directly by a host.
+// TODO: mark `log_utf8_string_impl` as #[wasm_bindgen], so it is polyfilled by bindgen
+// log_utf8_string should be provided directly by a host.
 #[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
-#[link(wasm_import_module = "__marine_host_api_v3")]
-extern "C" {
-    // Writes a byte string of size bytes that starts from ptr to a logger
-    #[link_name = "log_utf8_string"]
-    fn log_utf8_string_impl(level: i32, target: i32, msg_ptr: i32, msg_size: i32);
+marine_host_api::host_api_imports! {
+    extern "C" {
+        // Writes a byte string of size bytes that starts from ptr to a logger
+        #[link_name = "log_utf8_string"]
+        fn log_utf8_string_impl(level: i32, target: i32, msg_ptr: i32, msg_size: i32);
+    }
+}
+
+#[cfg(all(
+    feature = "marine-abi",
+    target_arch = "wasm32",
+    feature = "host-api-v4"
+))]
+marine_host_api::host_api_imports! {
+    extern "C" {
+        // Writes a batch of messages of size bytes that starts from ptr to a logger
+        #[link_name = "log_utf8_batch"]
+        fn log_utf8_batch_impl(batch_ptr: i32, batch_size: i32);
+    }
 }
 
 #[allow(dead_code)]
@@ -265,7 +635,11 @@ fn level_from_i32(level: i32) -> log::Level {
 mod tests {
     use super::WasmLogger;
     use super::LogDirective;
+    use super::LogLevels;
     use super::WasmLoggerBuilder;
+    use super::parse_log_directives;
+    use super::truncate_message;
+    use super::TRUNCATION_MARKER;
     use log::LevelFilter;
     use log::Log;
 
@@ -288,11 +662,11 @@ mod tests {
             LogDirective::new(module_2_name.to_string(), LevelFilter::Warn),
         ];
 
-        let logger = WasmLogger {
-            target_map: HashMap::new(),
+        let log_levels = LogLevels {
             modules_directives,
             default_log_level: LevelFilter::Error,
         };
+        let logger = WasmLogger::new(HashMap::new(), log_levels);
 
         let allowed_metadata = create_metadata(module_1_name, log::Level::Info);
         assert!(logger.enabled(&allowed_metadata));
@@ -314,11 +688,11 @@ mod tests {
     fn default_log_level() {
         let modules_directives = vec![LogDirective::new("module_1".to_string(), LevelFilter::Info)];
 
-        let logger = WasmLogger {
-            target_map: HashMap::new(),
+        let log_levels = LogLevels {
             modules_directives,
             default_log_level: LevelFilter::Warn,
         };
+        let logger = WasmLogger::new(HashMap::new(), log_levels);
 
         let module_name = "some_module";
         let allowed_metadata = create_metadata(module_name, log::Level::Warn);
@@ -347,4 +721,110 @@ mod tests {
         let not_allowed_metadata = create_metadata(module_2_name, log::Level::Info);
         assert!(!logger.enabled(&not_allowed_metadata));
     }
+
+    #[test]
+    fn set_level_at_runtime() {
+        let modules_directives = vec![LogDirective::new("module_1".to_string(), LevelFilter::Info)];
+        let mut log_levels = LogLevels {
+            modules_directives,
+            default_log_level: LevelFilter::Warn,
+        };
+
+        log_levels.set_level(Some("module_1"), LevelFilter::Trace);
+        log_levels.set_level(Some("module_1::db"), LevelFilter::Off);
+        log_levels.set_level(None, LevelFilter::Error);
+
+        assert!(log_levels.enabled(&create_metadata("module_1", log::Level::Trace)));
+        assert!(!log_levels.enabled(&create_metadata("module_1::db", log::Level::Error)));
+        assert!(!log_levels.enabled(&create_metadata("some_module", log::Level::Warn)));
+        assert_eq!(log_levels.max_log_level(), LevelFilter::Trace);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_follows_runtime_log_level() {
+        use super::update_log_level;
+        use crate::WasmTracingLayer;
+
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let logger = WasmLoggerBuilder::new()
+            .with_log_level(LevelFilter::Info)
+            .into_logger();
+        let log_levels = logger.log_levels.clone();
+        let subscriber = tracing_subscriber::registry().with(WasmTracingLayer::new(logger));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let mut enabled = Vec::new();
+            for level in [LevelFilter::Info, LevelFilter::Debug, LevelFilter::Info] {
+                update_log_level(&log_levels, None, level);
+                // the same callsite is hit after each update
+                enabled.push(tracing::enabled!(
+                    target: "runtime_module",
+                    tracing::Level::DEBUG
+                ));
+            }
+
+            assert_eq!(enabled, vec![false, true, false]);
+        });
+    }
+
+    #[test]
+    fn truncate_long_message() {
+        let msg = truncate_message("short".to_string(), 10);
+        assert_eq!(msg, "short");
+
+        let msg = truncate_message("a long message".to_string(), 6);
+        assert_eq!(msg, format!("a long{}", TRUNCATION_MARKER));
+
+        // truncation happens on a char boundary
+        let msg = truncate_message("ab\u{1F600}cd".to_string(), 4);
+        assert_eq!(msg, format!("ab{}", TRUNCATION_MARKER));
+    }
+
+    #[test]
+    fn parse_directives() {
+        let (default_log_level, modules_directives) =
+            parse_log_directives("info,my_crate::db=trace, noisy=off");
+
+        assert_eq!(default_log_level, Some(LevelFilter::Info));
+        assert_eq!(
+            modules_directives,
+            vec![
+                LogDirective::new("my_crate::db".to_string(), LevelFilter::Trace),
+                LogDirective::new("noisy".to_string(), LevelFilter::Off),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_single_level() {
+        let (default_log_level, modules_directives) = parse_log_directives("WARN");
+
+        assert_eq!(default_log_level, Some(LevelFilter::Warn));
+        assert!(modules_directives.is_empty());
+    }
+
+    #[test]
+    fn parse_bare_module_name() {
+        let (default_log_level, modules_directives) = parse_log_directives("my_crate,,");
+
+        assert_eq!(default_log_level, None);
+        assert_eq!(
+            modules_directives,
+            vec![LogDirective::new(
+                "my_crate".to_string(),
+                LevelFilter::Trace
+            )]
+        );
+    }
+
+    #[test]
+    fn parse_invalid_directives() {
+        let (default_log_level, modules_directives) =
+            parse_log_directives("my_crate=loud,=debug,error");
+
+        assert_eq!(default_log_level, Some(LevelFilter::Error));
+        assert!(modules_directives.is_empty());
+    }
 }
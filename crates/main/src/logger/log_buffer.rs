@@ -0,0 +1,156 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// Accumulates log records in the module memory to pass them to the host later, outside
+/// of hot paths. Each record is passed by a separate call, so record boundaries are kept.
+#[derive(Debug)]
+pub(super) struct LogBuffer {
+    capacity: usize,
+    size: usize,
+    records: Vec<LogRecord>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct LogRecord {
+    pub(super) level: i32,
+    pub(super) target: i32,
+    pub(super) msg: String,
+}
+
+impl LogBuffer {
+    pub(super) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            size: 0,
+            records: Vec::new(),
+        }
+    }
+
+    /// Appends a record to the buffer, returns true if the buffer is full and should be flushed.
+    pub(super) fn push(&mut self, level: i32, target: i32, msg: &str) -> bool {
+        let record = LogRecord {
+            level,
+            target,
+            msg: msg.to_string(),
+        };
+        self.records.push(record);
+        self.size += msg.len();
+
+        self.size >= self.capacity
+    }
+
+    pub(super) fn take_records(&mut self) -> Vec<LogRecord> {
+        self.size = 0;
+        std::mem::take(&mut self.records)
+    }
+}
+
+/// Encodes records into a batch passed to the host by one `log_utf8_batch` call: each record is
+/// its level, target and message size as little-endian 4-byte integers followed by the message.
+#[cfg_attr(
+    not(all(
+        feature = "marine-abi",
+        target_arch = "wasm32",
+        feature = "host-api-v4"
+    )),
+    allow(dead_code)
+)]
+pub(super) fn encode_batch(records: &[LogRecord]) -> Vec<u8> {
+    let size = records
+        .iter()
+        .map(|record| 3 * std::mem::size_of::<u32>() + record.msg.len())
+        .sum();
+
+    let mut batch = Vec::with_capacity(size);
+    for record in records {
+        batch.extend_from_slice(&record.level.to_le_bytes());
+        batch.extend_from_slice(&record.target.to_le_bytes());
+        batch.extend_from_slice(&(record.msg.len() as u32).to_le_bytes());
+        batch.extend_from_slice(record.msg.as_bytes());
+    }
+
+    batch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode_batch;
+    use super::LogBuffer;
+    use super::LogRecord;
+
+    #[test]
+    fn records_are_kept_separately() {
+        let mut buffer = LogBuffer::new(1024);
+
+        assert!(!buffer.push(3, 0, "message 1"));
+        assert!(!buffer.push(3, 0, "message 2"));
+        assert!(!buffer.push(1, 0, "message 3"));
+
+        let records = buffer.take_records();
+        let expected_records = vec![
+            LogRecord {
+                level: 3,
+                target: 0,
+                msg: "message 1".to_string(),
+            },
+            LogRecord {
+                level: 3,
+                target: 0,
+                msg: "message 2".to_string(),
+            },
+            LogRecord {
+                level: 1,
+                target: 0,
+                msg: "message 3".to_string(),
+            },
+        ];
+        assert_eq!(records, expected_records);
+        assert!(buffer.take_records().is_empty());
+    }
+
+    #[test]
+    fn full_buffer_requests_flush() {
+        let mut buffer = LogBuffer::new(16);
+
+        assert!(!buffer.push(3, 0, "message 1"));
+        assert!(buffer.push(3, 0, "message 2"));
+
+        buffer.take_records();
+        assert!(!buffer.push(3, 0, "message 3"));
+    }
+
+    #[test]
+    fn batch_layout() {
+        let records = vec![
+            LogRecord {
+                level: 3,
+                target: 0,
+                msg: "ab".to_string(),
+            },
+            LogRecord {
+                level: 1,
+                target: 2,
+                msg: String::new(),
+            },
+        ];
+
+        let expected_batch = vec![
+            3, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, b'a', b'b', //
+            1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        assert_eq!(encode_batch(&records), expected_batch);
+    }
+}
@@ -0,0 +1,106 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::flush_buffer;
+use super::write_log;
+use super::RUNTIME_LOG_BUFFER;
+
+use std::panic::PanicInfo;
+use std::sync::TryLockError;
+
+/// Installs a panic hook that passes the panic message, its location and the name of
+/// the current export function to the host with the error level. Buffered log messages are
+/// flushed before that, then the previously installed hook is called.
+pub(super) fn install() {
+    let previous_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        flush_before_report();
+
+        let report = panic_report(info);
+        write_log(log::Level::Error as i32, 0, &report);
+
+        previous_hook(info);
+    }));
+}
+
+/// The panic could happen while the buffer is locked by the logger itself,
+/// so it's flushed only if it isn't locked.
+fn flush_before_report() {
+    let buffer = match RUNTIME_LOG_BUFFER.get() {
+        Some(buffer) => buffer,
+        None => return,
+    };
+
+    match buffer.try_lock() {
+        Ok(mut buffer) => flush_buffer(&mut buffer),
+        Err(TryLockError::Poisoned(error)) => flush_buffer(&mut error.into_inner()),
+        Err(TryLockError::WouldBlock) => {}
+    }
+}
+
+fn panic_report(info: &PanicInfo<'_>) -> String {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+    let location = info
+        .location()
+        .map(|location| (location.file(), location.line(), location.column()));
+
+    format_panic_report(message, location, crate::export_hooks::current_export())
+}
+
+fn format_panic_report(
+    message: Option<&str>,
+    location: Option<(&str, u32, u32)>,
+    export_name: Option<&str>,
+) -> String {
+    let mut report = format!("panicked at '{}'", message.unwrap_or("Box<dyn Any>"));
+    if let Some((file, line, column)) = location {
+        report.push_str(&format!(", {}:{}:{}", file, line, column));
+    }
+    if let Some(export_name) = export_name {
+        report.push_str(&format!(" in export function '{}'", export_name));
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_panic_report;
+
+    #[test]
+    fn full_panic_report() {
+        let report = format_panic_report(
+            Some("index out of bounds"),
+            Some(("src/main.rs", 10, 5)),
+            Some("greeting"),
+        );
+        assert_eq!(
+            report,
+            "panicked at 'index out of bounds', src/main.rs:10:5 in export function 'greeting'"
+        );
+    }
+
+    #[test]
+    fn panic_report_without_export() {
+        let report = format_panic_report(None, None, None);
+        assert_eq!(report, "panicked at 'Box<dyn Any>'");
+    }
+}
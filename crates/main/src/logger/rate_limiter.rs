@@ -0,0 +1,137 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Limits the number of log messages per logging target with a token bucket and counts
+/// messages dropped due to this limit.
+#[derive(Debug)]
+pub(super) struct RateLimiter {
+    burst: u32,
+    messages_per_second: u32,
+    buckets: HashMap<String, TokenBucket>,
+    dropped_count: u64,
+    report_interval: Duration,
+    last_report: Instant,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub(super) fn new(
+        burst: u32,
+        messages_per_second: u32,
+        report_interval: Duration,
+        now: Instant,
+    ) -> Self {
+        Self {
+            burst,
+            messages_per_second,
+            buckets: HashMap::new(),
+            dropped_count: 0,
+            report_interval,
+            last_report: now,
+        }
+    }
+
+    /// Takes a token from the bucket of the supplied target, returns false and counts
+    /// the message as dropped if the bucket is empty.
+    pub(super) fn try_acquire(&mut self, target: &str, now: Instant) -> bool {
+        let burst = self.burst as f64;
+        if !self.buckets.contains_key(target) {
+            let bucket = TokenBucket {
+                tokens: burst,
+                last_refill: now,
+            };
+            self.buckets.insert(target.to_string(), bucket);
+        }
+        let bucket = self
+            .buckets
+            .get_mut(target)
+            .expect("bucket is inserted above");
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        let refilled = elapsed.as_secs_f64() * self.messages_per_second as f64;
+        bucket.tokens = (bucket.tokens + refilled).min(burst);
+        bucket.last_refill = now;
+
+        if bucket.tokens < 1.0 {
+            self.dropped_count += 1;
+            return false;
+        }
+
+        bucket.tokens -= 1.0;
+        true
+    }
+
+    /// Returns the number of messages dropped since the last report,
+    /// if there are any and the report interval has passed.
+    pub(super) fn take_dropped_report(&mut self, now: Instant) -> Option<u64> {
+        if self.dropped_count == 0
+            || now.saturating_duration_since(self.last_report) < self.report_interval
+        {
+            return None;
+        }
+
+        self.last_report = now;
+        Some(std::mem::take(&mut self.dropped_count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RateLimiter;
+
+    use std::time::Duration;
+    use std::time::Instant;
+
+    #[test]
+    fn drops_messages_above_burst() {
+        let now = Instant::now();
+        let mut limiter = RateLimiter::new(2, 1, Duration::from_secs(10), now);
+
+        assert!(limiter.try_acquire("module_1", now));
+        assert!(limiter.try_acquire("module_1", now));
+        assert!(!limiter.try_acquire("module_1", now));
+        // buckets are per target
+        assert!(limiter.try_acquire("module_2", now));
+
+        let now = now + Duration::from_secs(1);
+        assert!(limiter.try_acquire("module_1", now));
+        assert!(!limiter.try_acquire("module_1", now));
+    }
+
+    #[test]
+    fn reports_dropped_messages_periodically() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new(1, 1, Duration::from_secs(10), start);
+
+        assert!(limiter.try_acquire("module_1", start));
+        assert!(!limiter.try_acquire("module_1", start));
+        assert!(!limiter.try_acquire("module_1", start));
+        assert_eq!(limiter.take_dropped_report(start), None);
+
+        let now = start + Duration::from_secs(10);
+        assert_eq!(limiter.take_dropped_report(now), Some(2));
+        assert_eq!(limiter.take_dropped_report(now), None);
+    }
+}
@@ -68,7 +68,15 @@ pub unsafe fn release_objects() {
         while let Some(object) = objects.pop() {
             drop(object);
         }
-    })
+    });
+
+    #[cfg(feature = "arena-allocator")]
+    crate::arena_allocator::release_free_chunks();
+}
+
+/// Returns count of objects waiting for the next `release_objects` call.
+pub fn objects_to_release_count() -> usize {
+    OBJECTS_TO_RELEASE.with(|objects| objects.borrow().len())
 }
 
 pub fn add_object_to_release(object: Box<dyn Any>) {
@@ -0,0 +1,354 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! This module allows forwarding events from crate [`tracing`] to the host. It is implemented
+//! as a [`Layer`] on top of the Wasm logger, so it uses the same log level directives
+//! (including the ones from the WASM_LOG environment variable) and the same target map.
+//!
+//! # Examples
+//!
+//! ```ignore
+//!     use marine_rs_sdk::WasmLoggerBuilder;
+//!
+//!     fn main() {
+//!         WasmLoggerBuilder::new()
+//!             .with_log_level(log::LevelFilter::Info)
+//!             .build_tracing_layer()
+//!             .with_span_events(true)
+//!             .init()
+//!             .unwrap();
+//!
+//!         tracing::info!(user = "alice", "This message will be logged.");
+//!         tracing::trace!("This message will not be logged.");
+//!     }
+//! ```
+//!
+//! [`tracing`]: https://docs.rs/tracing
+//! [`Layer`]: https://docs.rs/tracing-subscriber/0.3/tracing_subscriber/layer/trait.Layer.html
+
+use crate::logger::WasmLogger;
+
+use log::Log;
+use tracing_core::dispatcher::SetGlobalDefaultError;
+use tracing_core::field::Field;
+use tracing_core::field::Visit;
+use tracing_core::span;
+use tracing_core::Event;
+use tracing_core::Interest;
+use tracing_core::Level;
+use tracing_core::Metadata;
+use tracing_core::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::layer::Layer;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+
+use std::fmt;
+use std::fmt::Write;
+use std::time::Duration;
+use std::time::Instant;
+
+/// The tracing layer that forwards events to the host through the Wasm logger.
+///
+/// It could be obtained from [`WasmLoggerBuilder::build_tracing_layer`] and then either
+/// installed as the global subscriber by [`WasmTracingLayer::init`] or composed
+/// with other layers.
+///
+/// [`WasmLoggerBuilder::build_tracing_layer`]: crate::WasmLoggerBuilder::build_tracing_layer
+pub struct WasmTracingLayer {
+    logger: WasmLogger,
+    span_events: bool,
+}
+
+/// Time spent inside a span, it's stored in the span extensions.
+struct SpanTimings {
+    last_entered: Option<Instant>,
+    busy: Duration,
+}
+
+impl WasmTracingLayer {
+    pub(crate) fn new(logger: WasmLogger) -> Self {
+        Self {
+            logger,
+            span_events: false,
+        }
+    }
+
+    /// Log span enter and exit with the time spent inside a span.
+    pub fn with_span_events(mut self, span_events: bool) -> Self {
+        self.span_events = span_events;
+        self
+    }
+
+    /// Set this layer over the registry as the global default subscriber.
+    pub fn init(self) -> Result<(), SetGlobalDefaultError> {
+        let subscriber = tracing_subscriber::registry().with(self);
+        tracing_core::dispatcher::set_global_default(subscriber.into())
+    }
+
+    fn is_enabled(&self, metadata: &Metadata<'_>) -> bool {
+        let metadata = log::MetadataBuilder::new()
+            .level(to_log_level(metadata.level()))
+            .target(metadata.target())
+            .build();
+
+        self.logger.enabled(&metadata)
+    }
+
+    fn forward(&self, metadata: &Metadata<'_>, args: fmt::Arguments<'_>) {
+        let record = log::Record::builder()
+            .level(to_log_level(metadata.level()))
+            .target(metadata.target())
+            .args(args)
+            .build();
+
+        self.logger.log(&record);
+    }
+}
+
+impl<S> Layer<S> for WasmTracingLayer
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    // log levels could be adjusted at runtime, so interest can't be cached for a callsite
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+        self.is_enabled(metadata)
+    }
+
+    fn on_new_span(&self, _attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if !self.span_events {
+            return;
+        }
+
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanTimings {
+                last_entered: None,
+                busy: Duration::ZERO,
+            });
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut message = String::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                let _ = write!(message, "{}:", span.name());
+            }
+            if !message.is_empty() {
+                message.push(' ');
+            }
+        }
+
+        let mut visitor = FieldsVisitor::default();
+        event.record(&mut visitor);
+        visitor.write_to(&mut message);
+
+        self.forward(event.metadata(), format_args!("{}", message));
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if !self.span_events {
+            return;
+        }
+
+        let span = match ctx.span(id) {
+            Some(span) => span,
+            None => return,
+        };
+
+        if let Some(timings) = span.extensions_mut().get_mut::<SpanTimings>() {
+            timings.last_entered = Some(Instant::now());
+        }
+
+        self.forward(span.metadata(), format_args!("-> {}", span.name()));
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if !self.span_events {
+            return;
+        }
+
+        let span = match ctx.span(id) {
+            Some(span) => span,
+            None => return,
+        };
+
+        let elapsed = span
+            .extensions_mut()
+            .get_mut::<SpanTimings>()
+            .and_then(|timings| {
+                let elapsed = timings.last_entered.take()?.elapsed();
+                timings.busy += elapsed;
+                Some(elapsed)
+            })
+            .unwrap_or_default();
+
+        self.forward(
+            span.metadata(),
+            format_args!("<- {} elapsed: {:?}", span.name(), elapsed),
+        );
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        if !self.span_events {
+            return;
+        }
+
+        let span = match ctx.span(&id) {
+            Some(span) => span,
+            None => return,
+        };
+
+        let busy = span
+            .extensions()
+            .get::<SpanTimings>()
+            .map(|timings| timings.busy)
+            .unwrap_or_default();
+
+        self.forward(
+            span.metadata(),
+            format_args!("-- {} closed, busy: {:?}", span.name(), busy),
+        );
+    }
+}
+
+/// Collects event fields into a message followed by `key=value` pairs.
+#[derive(Default)]
+struct FieldsVisitor {
+    message: String,
+    fields: String,
+}
+
+impl FieldsVisitor {
+    fn write_to(self, output: &mut String) {
+        output.push_str(&self.message);
+        if self.message.is_empty() {
+            output.push_str(self.fields.trim_start());
+        } else {
+            output.push_str(&self.fields);
+        }
+    }
+}
+
+impl Visit for FieldsVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+fn to_log_level(level: &Level) -> log::Level {
+    match *level {
+        Level::ERROR => log::Level::Error,
+        Level::WARN => log::Level::Warn,
+        Level::INFO => log::Level::Info,
+        Level::DEBUG => log::Level::Debug,
+        Level::TRACE => log::Level::Trace,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FieldsVisitor;
+    use crate::WasmLoggerBuilder;
+
+    use log::LevelFilter;
+    use tracing::Level;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn enabled_by_module_name() {
+        let layer = WasmLoggerBuilder::new()
+            .with_log_level(LevelFilter::Error)
+            .filter("module_1", LevelFilter::Info)
+            .filter("module_1::inner", LevelFilter::Warn)
+            .build_tracing_layer();
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(tracing::enabled!(target: "module_1", Level::INFO));
+            assert!(!tracing::enabled!(target: "module_1", Level::DEBUG));
+            assert!(tracing::enabled!(target: "module_1::inner", Level::WARN));
+            assert!(!tracing::enabled!(target: "module_1::inner", Level::INFO));
+            assert!(tracing::enabled!(target: "some_module", Level::ERROR));
+            assert!(!tracing::enabled!(target: "some_module", Level::WARN));
+        });
+    }
+
+    #[test]
+    fn message_goes_before_fields() {
+        use tracing_core::field::Visit;
+        use tracing_core::field::FieldSet;
+        use tracing_core::metadata::Kind;
+        use tracing_core::Metadata;
+
+        struct TestCallsite;
+        impl tracing_core::Callsite for TestCallsite {
+            fn set_interest(&self, _: tracing_core::Interest) {}
+            fn metadata(&self) -> &Metadata<'_> {
+                &METADATA
+            }
+        }
+        static CALLSITE: TestCallsite = TestCallsite;
+        static METADATA: Metadata<'static> = Metadata::new(
+            "test event",
+            "tracing_layer",
+            Level::INFO,
+            None,
+            None,
+            None,
+            FieldSet::new(
+                &["user", "message"],
+                tracing_core::identify_callsite!(&CALLSITE),
+            ),
+            Kind::EVENT,
+        );
+
+        let field_set = METADATA.fields();
+        let user_field = field_set.field("user").unwrap();
+        let message_field = field_set.field("message").unwrap();
+
+        let mut visitor = FieldsVisitor::default();
+        visitor.record_str(&user_field, "alice");
+        visitor.record_debug(&message_field, &format_args!("user logged in"));
+
+        let mut output = String::new();
+        visitor.write_to(&mut output);
+        assert_eq!(output, "user logged in user=alice");
+
+        let mut visitor = FieldsVisitor::default();
+        visitor.record_debug(&user_field, &42);
+
+        let mut output = String::new();
+        visitor.write_to(&mut output);
+        assert_eq!(output, "user=42");
+    }
+}
@@ -0,0 +1,198 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A global allocator wrapper that counts allocations, enabled by the `memory-stats` feature.
+//! It wraps the arena allocator, if the `arena-allocator` feature is enabled, and the system
+//! allocator otherwise.
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+/// Allocation statistics collected by a [`TrackingAllocator`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocationStats {
+    /// Bytes of currently live allocations.
+    pub live_bytes: usize,
+
+    /// Maximum of live_bytes since the start of the module.
+    pub peak_bytes: usize,
+
+    /// Count of allocations since the start of the module, reallocations aren't counted.
+    pub allocations: usize,
+
+    /// Count of currently live allocations.
+    pub live_allocations: usize,
+}
+
+/// Passes all requests to the inner allocator and counts them.
+pub struct TrackingAllocator<A> {
+    inner: A,
+    live_bytes: AtomicUsize,
+    peak_bytes: AtomicUsize,
+    allocations: AtomicUsize,
+    live_allocations: AtomicUsize,
+}
+
+impl<A> TrackingAllocator<A> {
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            live_bytes: AtomicUsize::new(0),
+            peak_bytes: AtomicUsize::new(0),
+            allocations: AtomicUsize::new(0),
+            live_allocations: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn stats(&self) -> AllocationStats {
+        AllocationStats {
+            live_bytes: self.live_bytes.load(Ordering::Relaxed),
+            peak_bytes: self.peak_bytes.load(Ordering::Relaxed),
+            allocations: self.allocations.load(Ordering::Relaxed),
+            live_allocations: self.live_allocations.load(Ordering::Relaxed),
+        }
+    }
+
+    fn add_live_bytes(&self, size: usize) {
+        let live_bytes = self.live_bytes.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak_bytes.fetch_max(live_bytes, Ordering::Relaxed);
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            self.allocations.fetch_add(1, Ordering::Relaxed);
+            self.live_allocations.fetch_add(1, Ordering::Relaxed);
+            self.add_live_bytes(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.allocations.fetch_add(1, Ordering::Relaxed);
+            self.live_allocations.fetch_add(1, Ordering::Relaxed);
+            self.add_live_bytes(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        self.live_allocations.fetch_sub(1, Ordering::Relaxed);
+        self.live_bytes.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            self.live_bytes.fetch_sub(layout.size(), Ordering::Relaxed);
+            self.add_live_bytes(new_size);
+        }
+
+        new_ptr
+    }
+}
+
+#[cfg(all(
+    feature = "marine-abi",
+    target_arch = "wasm32",
+    feature = "arena-allocator"
+))]
+pub(crate) type InnerAllocator = crate::arena_allocator::ArenaAllocator;
+
+#[cfg(all(
+    feature = "marine-abi",
+    target_arch = "wasm32",
+    not(feature = "arena-allocator")
+))]
+pub(crate) type InnerAllocator = std::alloc::System;
+
+#[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
+const fn inner_allocator() -> InnerAllocator {
+    #[cfg(feature = "arena-allocator")]
+    return crate::arena_allocator::ArenaAllocator::new();
+
+    #[cfg(not(feature = "arena-allocator"))]
+    return std::alloc::System;
+}
+
+#[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator<InnerAllocator> = TrackingAllocator::new(inner_allocator());
+
+#[cfg(all(
+    feature = "marine-abi",
+    target_arch = "wasm32",
+    feature = "arena-allocator"
+))]
+pub(crate) fn global_allocator() -> &'static TrackingAllocator<InnerAllocator> {
+    &ALLOCATOR
+}
+
+/// Returns statistics of the global allocator.
+#[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
+pub fn allocation_stats() -> AllocationStats {
+    ALLOCATOR.stats()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AllocationStats;
+    use super::TrackingAllocator;
+    use std::alloc::GlobalAlloc;
+    use std::alloc::Layout;
+    use std::alloc::System;
+
+    #[test]
+    fn allocations_are_counted() {
+        let allocator = TrackingAllocator::new(System);
+        let layout = Layout::from_size_align(100, 8).unwrap();
+
+        unsafe {
+            let first = allocator.alloc(layout);
+            let second = allocator.alloc_zeroed(layout);
+            let second = allocator.realloc(second, layout, 300);
+            assert_eq!(
+                allocator.stats(),
+                AllocationStats {
+                    live_bytes: 400,
+                    peak_bytes: 400,
+                    allocations: 2,
+                    live_allocations: 2,
+                }
+            );
+
+            allocator.dealloc(first, layout);
+            allocator.dealloc(second, Layout::from_size_align(300, 8).unwrap());
+            let stats = allocator.stats();
+            assert_eq!(stats.live_bytes, 0);
+            assert_eq!(stats.live_allocations, 0);
+            assert_eq!(stats.peak_bytes, 400);
+        }
+    }
+}
@@ -73,12 +73,51 @@ pub(crate) struct AstExternMod {
     pub(crate) imports: Vec<AstExternFn>,
 }
 
+/// Arguments of the #[marine] macro applied to an export function.
+#[derive(Clone, Default)]
+pub(crate) struct AstFnAttrs {
+    /// Peers allowed to call the function, if any of them is specified.
+    pub(crate) only_owner: bool,
+    pub(crate) only_host: bool,
+    pub(crate) allowed_peers: Option<Vec<String>>,
+}
+
+impl AstFnAttrs {
+    /// Returns true if callers of the function should be checked.
+    pub(crate) fn has_caller_policy(&self) -> bool {
+        self.only_owner || self.only_host || self.allowed_peers.is_some()
+    }
+}
+
+/// Required origin of an export function argument, specified by
+/// `#[marine(require_tetraplet(...))]` applied to this argument.
+#[derive(Clone, Default)]
+pub(crate) struct AstTetrapletRequirement {
+    pub(crate) arg_index: usize,
+    pub(crate) peer_pk: Option<String>,
+    pub(crate) service_id: Option<String>,
+    pub(crate) function_name: Option<String>,
+    pub(crate) no_lens: bool,
+    /// Argument should be a string literal from the script of the init peer.
+    pub(crate) literal: bool,
+}
+
 #[derive(Clone)]
 pub(crate) struct AstFn {
     pub(crate) signature: AstFnSignature,
+    pub(crate) attrs: AstFnAttrs,
+    pub(crate) tetraplet_requirements: Vec<AstTetrapletRequirement>,
     pub(crate) original: syn::ItemFn,
 }
 
+impl AstFn {
+    /// Returns true if export errors are converted into the return value with FromExportError,
+    /// so the return type of the function should implement it.
+    pub(crate) fn converts_export_errors(&self) -> bool {
+        self.attrs.has_caller_policy() || !self.tetraplet_requirements.is_empty()
+    }
+}
+
 #[derive(Clone)]
 pub(crate) enum MarineAst {
     Function(Box<AstFn>),
@@ -68,9 +68,22 @@ pub struct ExternModType {
     pub imports: Vec<ExternFnType>,
 }
 
+/// Peers allowed to call an export function, the caller is allowed if it matches any of them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallerPolicy {
+    /// The service creator is allowed, specified by `#[marine(only_owner)]`.
+    pub owner: bool,
+    /// The host is allowed, specified by `#[marine(only_host)]`.
+    pub host: bool,
+    /// Peers allowed by `#[marine(allow_peers = [...])]`.
+    pub peers: Vec<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FnType {
     pub signature: FnSignature,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caller_policy: Option<CallerPolicy>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -109,9 +122,18 @@ impl From<AstRecord> for SDKAst {
 
 impl From<AstFn> for FnType {
     fn from(ast_fn_item: AstFn) -> Self {
+        let attrs = &ast_fn_item.attrs;
+        let caller_policy = attrs.has_caller_policy().then(|| CallerPolicy {
+            owner: attrs.only_owner,
+            host: attrs.only_host,
+            peers: attrs.allowed_peers.clone().unwrap_or_default(),
+        });
         let signature = ast_fn_item.signature.into();
 
-        Self { signature }
+        Self {
+            signature,
+            caller_policy,
+        }
     }
 }
 
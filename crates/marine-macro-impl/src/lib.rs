@@ -41,6 +41,7 @@ mod wasm_type;
 
 pub use export_ast_types::*;
 pub use crate::marine_macro_impl::marine;
+pub use crate::marine_macro_impl::marine_with_attrs;
 pub use parsed_type::ParsedType;
 pub use parsed_type::PassingStyle;
 pub use token_stream_generator::GENERATED_WRAPPER_FUNC_PREFIX;
@@ -49,5 +50,5 @@ pub use token_stream_generator::GENERATED_GLOBAL_PREFIX;
 pub use wasm_type::RustType;
 
 pub const GENERATED_SECTION_PREFIX_FCE: &str = "__fce_generated_section__";
-pub const MARINE_HOST_API_NAMESPACE_PREFIX: &str = "__marine_host_api_v";
-pub const MARINE_HOST_API_VERSION: u32 = 1;
+pub const MARINE_HOST_API_NAMESPACE_PREFIX: &str = marine_host_api::HOST_API_NAMESPACE_PREFIX;
+pub const MARINE_HOST_API_VERSION: u32 = marine_host_api::HOST_API_VERSION;
@@ -14,6 +14,7 @@
  * limitations under the License.
  */
 
+use crate::parse_macro_input::apply_fn_attrs;
 use crate::parse_macro_input::ParseMacroInput;
 
 use proc_macro2::TokenStream;
@@ -21,9 +22,16 @@ use quote::ToTokens;
 use syn::Result;
 
 pub fn marine(tokens: TokenStream) -> Result<TokenStream> {
+    marine_with_attrs(TokenStream::new(), tokens)
+}
+
+/// The same as [`marine`], but also takes arguments of the macro, e.g. `only_owner` in
+/// `#[marine(only_owner)]`.
+pub fn marine_with_attrs(attrs: TokenStream, tokens: TokenStream) -> Result<TokenStream> {
     let item = syn::parse2::<syn::Item>(tokens)?;
     // convert proc_macro2 token to internal AST type
-    let marine_ast_item = item.parse_macro_input()?;
+    let mut marine_ast_item = item.parse_macro_input()?;
+    apply_fn_attrs(&mut marine_ast_item, attrs)?;
 
     // convert internal AST type to sequence of tokens
     let mut tokens = TokenStream::new();
@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+mod arg_attrs;
+mod fn_attrs;
 mod item_fn;
 mod item_foreign_mod;
 mod item_record;
@@ -21,6 +23,8 @@ mod utils;
 
 use crate::ast_types::MarineAst;
 
+pub(crate) use fn_attrs::apply_fn_attrs;
+
 pub(crate) trait ParseMacroInput {
     fn parse_macro_input(self) -> syn::Result<MarineAst>;
 }
@@ -0,0 +1,111 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::ast_types::AstTetrapletRequirement;
+use crate::syn_error;
+
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::Result;
+
+/// Removes #[marine(...)] attributes from arguments of an export function and parses them
+/// into tetraplet requirements.
+pub(super) fn take_tetraplet_requirements(
+    signature: &mut syn::Signature,
+) -> Result<Vec<AstTetrapletRequirement>> {
+    let mut requirements = Vec::new();
+
+    for (arg_index, arg) in signature.inputs.iter_mut().enumerate() {
+        let pat = match arg {
+            syn::FnArg::Typed(pat) => pat,
+            // self arguments are rejected while parsing the signature
+            syn::FnArg::Receiver(_) => continue,
+        };
+
+        let (marine_attrs, other_attrs) = std::mem::take(&mut pat.attrs)
+            .into_iter()
+            .partition::<Vec<_>, _>(|attr| attr.path.is_ident("marine"));
+        pat.attrs = other_attrs;
+
+        if let Some(attr) = marine_attrs.get(1) {
+            return syn_error!(attr.span(), "`require_tetraplet` is specified twice");
+        }
+
+        if let Some(attr) = marine_attrs.first() {
+            let mut requirement = attr.parse_args_with(parse_requirement)?;
+            requirement.arg_index = arg_index;
+            requirements.push(requirement);
+        }
+    }
+
+    Ok(requirements)
+}
+
+/// Parses `require_tetraplet(peer_id = "..", service_id = "..", function_name = "..", no_lens, literal)`,
+/// all the requirement arguments are optional.
+fn parse_requirement(input: ParseStream<'_>) -> Result<AstTetrapletRequirement> {
+    let name = input.parse::<syn::Ident>()?;
+    if name != "require_tetraplet" {
+        return syn_error!(
+            name.span(),
+            format!("unknown #[marine] argument attribute `{}`", name)
+        );
+    }
+
+    let content;
+    syn::parenthesized!(content in input);
+
+    let mut requirement = AstTetrapletRequirement::default();
+    while !content.is_empty() {
+        let key = content.parse::<syn::Ident>()?;
+        match key.to_string().as_str() {
+            "peer_id" => set_once(&mut requirement.peer_pk, &key, &content)?,
+            "service_id" => set_once(&mut requirement.service_id, &key, &content)?,
+            "function_name" => set_once(&mut requirement.function_name, &key, &content)?,
+            "no_lens" => requirement.no_lens = true,
+            "literal" => requirement.literal = true,
+            _ => {
+                return syn_error!(
+                    key.span(),
+                    format!("unknown `require_tetraplet` argument `{}`", key)
+                )
+            }
+        }
+
+        if content.is_empty() {
+            break;
+        }
+        content.parse::<syn::Token![,]>()?;
+    }
+
+    if !input.is_empty() {
+        return Err(input.error("unexpected tokens after `require_tetraplet`"));
+    }
+
+    Ok(requirement)
+}
+
+fn set_once(value: &mut Option<String>, key: &syn::Ident, input: ParseStream<'_>) -> Result<()> {
+    if value.is_some() {
+        return syn_error!(key.span(), format!("`{}` is specified twice", key));
+    }
+
+    input.parse::<syn::Token![=]>()?;
+    let literal = input.parse::<syn::LitStr>()?;
+    *value = Some(literal.value());
+
+    Ok(())
+}
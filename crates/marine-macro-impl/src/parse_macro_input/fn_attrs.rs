@@ -0,0 +1,117 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::utils::check_returns_value;
+use crate::ast_types::AstFn;
+use crate::ast_types::AstFnAttrs;
+use crate::ast_types::MarineAst;
+use crate::syn_error;
+
+use proc_macro2::TokenStream;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::Result;
+
+/// Parses arguments of the #[marine] macro and applies them to an export function,
+/// other items don't accept any arguments.
+pub(crate) fn apply_fn_attrs(marine_ast: &mut MarineAst, attrs: TokenStream) -> Result<()> {
+    if attrs.is_empty() {
+        return Ok(());
+    }
+
+    let ast_fn = match marine_ast {
+        MarineAst::Function(ast_fn) => ast_fn,
+        _ => {
+            return syn_error!(
+                attrs.span(),
+                "#[marine] arguments could be specified only for export functions"
+            )
+        }
+    };
+
+    ast_fn.attrs = syn::parse2(attrs)?;
+    check_attrs(ast_fn)
+}
+
+impl Parse for AstFnAttrs {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let mut attrs = AstFnAttrs::default();
+        let mut specified = Vec::new();
+
+        while !input.is_empty() {
+            let name = input.parse::<syn::Ident>()?;
+            if specified.contains(&name) {
+                return syn_error!(name.span(), format!("`{}` is specified twice", name));
+            }
+
+            match name.to_string().as_str() {
+                "only_owner" => attrs.only_owner = true,
+                "only_host" => attrs.only_host = true,
+                "allow_peers" => attrs.allowed_peers = Some(parse_peers(input)?),
+                _ => {
+                    return syn_error!(
+                        name.span(),
+                        format!("unknown #[marine] argument `{}`", name)
+                    )
+                }
+            }
+            specified.push(name);
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<syn::Token![,]>()?;
+        }
+
+        Ok(attrs)
+    }
+}
+
+/// Parses `= ["peer_id_1", "peer_id_2"]`.
+fn parse_peers(input: ParseStream<'_>) -> Result<Vec<String>> {
+    input.parse::<syn::Token![=]>()?;
+
+    let content;
+    syn::bracketed!(content in input);
+    let peers = content.parse_terminated::<_, syn::Token![,]>(<syn::LitStr as Parse>::parse)?;
+
+    Ok(peers.into_iter().map(|peer| peer.value()).collect())
+}
+
+fn check_attrs(ast_fn: &AstFn) -> Result<()> {
+    let AstFnAttrs {
+        only_owner,
+        only_host,
+        allowed_peers,
+    } = &ast_fn.attrs;
+
+    let attr_name = if *only_owner {
+        "only_owner"
+    } else if *only_host {
+        "only_host"
+    } else if allowed_peers.is_some() {
+        "allow_peers"
+    } else {
+        return Ok(());
+    };
+
+    check_returns_value(
+        &ast_fn.original.sig,
+        &ast_fn.signature.output_type,
+        attr_name,
+    )
+}
@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+use super::arg_attrs::take_tetraplet_requirements;
+use super::utils::check_returns_value;
 use super::ParseMacroInput;
 use crate::ast_types;
 use crate::ParsedType;
@@ -26,7 +28,9 @@ use syn::Result;
 use syn::spanned::Spanned;
 
 impl ParseMacroInput for syn::ItemFn {
-    fn parse_macro_input(self) -> Result<MarineAst> {
+    fn parse_macro_input(mut self) -> Result<MarineAst> {
+        // argument attributes should be removed from the original function
+        let tetraplet_requirements = take_tetraplet_requirements(&mut self.sig)?;
         let signature = try_to_ast_signature(self.sig.clone(), self.vis.clone())?;
 
         // this check specific only for export functions
@@ -37,9 +41,14 @@ impl ParseMacroInput for syn::ItemFn {
 
         check_args(parsed_args)?;
         check_output_type(&signature.output_type, self.sig.output.span())?;
+        if !tetraplet_requirements.is_empty() {
+            check_returns_value(&self.sig, &signature.output_type, "require_tetraplet")?;
+        }
 
         let ast_fn = AstFn {
             signature,
+            attrs: <_>::default(),
+            tetraplet_requirements,
             original: self,
         };
         let ast_fn = MarineAst::Function(Box::new(ast_fn));
@@ -17,6 +17,10 @@
 use crate::ParsedType;
 use crate::parsed_type::PassingStyle;
 use crate::parsed_type::passing_style_of;
+use crate::syn_error;
+
+use syn::spanned::Spanned;
+use syn::Result;
 
 /// Checks whether a type contains a reference in one of types.
 pub(super) fn contain_inner_ref(ty: &ParsedType) -> bool {
@@ -31,3 +35,27 @@ pub(super) fn contain_inner_ref(ty: &ParsedType) -> bool {
         _ => false,
     }
 }
+
+/// Checks that an export function returns a value, so that errors of the checks enabled
+/// by `attr_name` could be converted into it with FromExportError.
+pub(super) fn check_returns_value(
+    signature: &syn::Signature,
+    output_type: &Option<ParsedType>,
+    attr_name: &str,
+) -> Result<()> {
+    let span = match &signature.output {
+        syn::ReturnType::Type(_, ty) => ty.span(),
+        syn::ReturnType::Default => signature.ident.span(),
+    };
+
+    match output_type.as_ref().map(passing_style_of) {
+        Some(PassingStyle::ByValue) => Ok(()),
+        _ => syn_error!(
+            span,
+            format!(
+                "export functions with `{}` should return a value implementing FromExportError",
+                attr_name
+            )
+        ),
+    }
+}
@@ -31,6 +31,9 @@ pub(crate) struct FnPrologDescriptor {
     pub(crate) prolog: proc_macro2::TokenStream,
     pub(crate) converted_arg_idents: Vec<syn::Ident>,
     pub(crate) args: Vec<proc_macro2::TokenStream>,
+    /// (pointer, size, name, element type) of strings and vectors that should be checked before lifting,
+    /// because host passes a null pointer if it failed to allocate memory for them.
+    pub(crate) allocated_args: Vec<proc_macro2::TokenStream>,
 }
 
 /// This trait could be used to generate various parts needed to construct prolog of an export
@@ -55,6 +58,7 @@ impl FnPrologGlueCodeGenerator for Vec<AstFnArgument> {
         let mut prolog = proc_macro2::TokenStream::new();
         let mut converted_arg_idents = Vec::with_capacity(self.len());
         let mut args: Vec<proc_macro2::TokenStream> = Vec::with_capacity(self.len());
+        let mut allocated_args = Vec::new();
 
         let mut input_type_id = 0;
         for arg in self {
@@ -64,6 +68,19 @@ impl FnPrologGlueCodeGenerator for Vec<AstFnArgument> {
                 type_lifter_glue_code,
             } = generate_type_lifting_prolog(&arg.ty, passing_style, input_type_id, input_type_id);
 
+            // records are passed by a pointer only, and a zero pointer is valid for empty records,
+            // so failed allocations can't be detected for them
+            let is_record = matches!(arg.ty, ParsedType::Record(..));
+            if arg.ty.is_complex_type() && !is_record {
+                let ptr = new_ident!(format!("arg_{}", input_type_id));
+                let size = new_ident!(format!("arg_{}", input_type_id + 1));
+                let name = &arg.name;
+                let element_type = new_ident!(allocation_element_type(&arg.ty));
+                allocated_args.push(quote! {
+                    (#ptr, #size, #name, marine_rs_sdk::AllocationElementType::#element_type)
+                });
+            }
+
             let curr_raw_arg_types = arg.generate_arguments();
             let arg = quote! { #passing_style #converted_arg_ident };
             args.push(arg);
@@ -87,6 +104,7 @@ impl FnPrologGlueCodeGenerator for Vec<AstFnArgument> {
             prolog,
             converted_arg_idents,
             args,
+            allocated_args,
         }
     }
 }
@@ -167,6 +185,32 @@ fn generate_type_lifting_prolog(
     }
 }
 
+/// Returns the name of AllocationElementType variant host uses to allocate a complex argument.
+fn allocation_element_type(ty: &ParsedType) -> &'static str {
+    match ty {
+        ParsedType::Utf8Str(_) | ParsedType::Utf8String(_) => "U8",
+        ParsedType::Vector(ty, _) => match ty.as_ref() {
+            ParsedType::Boolean(_) => "Boolean",
+            ParsedType::U8(_) => "U8",
+            ParsedType::U16(_) => "U16",
+            ParsedType::U32(_) => "U32",
+            ParsedType::U64(_) => "U64",
+            ParsedType::I8(_) => "I8",
+            ParsedType::I16(_) => "I16",
+            ParsedType::I32(_) => "I32",
+            ParsedType::I64(_) => "I64",
+            ParsedType::F32(_) => "F32",
+            ParsedType::F64(_) => "F64",
+            // strings, vectors and records are passed as their pointers and sizes
+            ParsedType::Utf8Str(_)
+            | ParsedType::Utf8String(_)
+            | ParsedType::Vector(..)
+            | ParsedType::Record(..) => "Pointer",
+        },
+        _ => panic!("allocation element type is defined only for strings and vectors"),
+    }
+}
+
 fn converted_arg_modifier(passing_style: &PassingStyle) -> proc_macro2::TokenStream {
     match passing_style {
         PassingStyle::ByMutRef => quote! { mut },
@@ -20,6 +20,8 @@ use crate::parsed_type::FnEpilogDescriptor;
 use crate::parsed_type::FnEpilogIngredients;
 use crate::parsed_type::FnPrologGlueCodeGenerator;
 use crate::parsed_type::FnPrologDescriptor;
+use crate::parsed_type::PassingStyle;
+use crate::parsed_type::passing_style_of;
 
 use crate::new_ident;
 
@@ -52,6 +54,7 @@ impl quote::ToTokens for ast_types::AstFn {
             prolog,
             converted_arg_idents,
             args,
+            allocated_args,
         } = &signature.arguments.generate_prolog();
 
         let epilog_ingredients = FnEpilogIngredients {
@@ -68,6 +71,18 @@ impl quote::ToTokens for ast_types::AstFn {
         } = epilog_ingredients.generate_fn_epilog();
 
         let original_func = &self.original;
+        let original_call = quote::quote! { #original_func_ident(#(#args), *) };
+        let checks = self.generate_checks();
+        // functions with checks return a value, it's checked while parsing
+        let original_call = match &original_func.sig.output {
+            syn::ReturnType::Type(_, ty) if !checks.is_empty() => {
+                generate_checked_call(original_call, checks, ty)
+            }
+            _ => original_call,
+        };
+
+        let lifted_call =
+            self.generate_lifted_call(allocated_args, prolog, &return_expression, original_call);
 
         let glue_code = quote::quote! {
             #original_func
@@ -78,11 +93,15 @@ impl quote::ToTokens for ast_types::AstFn {
             #[doc(hidden)]
             #[allow(clippy::all)]
             pub unsafe fn #func_name(#(#raw_arg_names: #raw_arg_types),*) #fn_return_type {
-                // arguments conversation from Wasm types to Rust types
-                #prolog
+                marine_rs_sdk::internal::enter_export(#export_func_name);
+
+                // checking that host allocated memory for complex arguments,
+                // converting arguments from Wasm types to Rust types
+                // and calling the original function with converted args
+                #lifted_call
 
-                // calling the original function with converted args
-                #return_expression #original_func_ident(#(#args), *);
+                // flushing buffered logs and other per-call state
+                marine_rs_sdk::internal::exit_export();
 
                 // return value conversation from Rust type to a Wasm type
                 #epilog
@@ -101,3 +120,166 @@ impl quote::ToTokens for ast_types::AstFn {
         tokens.extend(glue_code);
     }
 }
+
+impl ast_types::AstFn {
+    /// Generates checks of the caller and arguments, each of them returns
+    /// Result<(), ExportError> and uses call_parameters.
+    fn generate_checks(&self) -> Vec<TokenStream> {
+        let mut checks = Vec::new();
+        if self.attrs.has_caller_policy() {
+            checks.push(generate_caller_check(&self.attrs));
+        }
+        if !self.tetraplet_requirements.is_empty() {
+            checks.push(generate_tetraplet_checks(
+                &self.signature,
+                &self.tetraplet_requirements,
+            ));
+        }
+
+        checks
+    }
+
+    /// Generates lifting of arguments and the original function call preceded by a check of
+    /// complex arguments allocation. If the check fails, the error is converted with
+    /// FromExportError into the returned value or reported for functions without return values,
+    /// so the call goes through the usual epilog. Errors of functions returning other types or
+    /// references are reported and the call is aborted, because there is no value to return.
+    fn generate_lifted_call(
+        &self,
+        allocated_args: &[TokenStream],
+        prolog: &TokenStream,
+        return_expression: &TokenStream,
+        original_call: TokenStream,
+    ) -> TokenStream {
+        if allocated_args.is_empty() {
+            return quote::quote! {
+                #prolog
+                #return_expression #original_call;
+            };
+        }
+
+        let export_func_name = &self.signature.name;
+        let allocation_check = quote::quote! {
+            marine_rs_sdk::internal::check_allocations(&[#(#allocated_args),*])
+        };
+        let abort = quote::quote! {
+            marine_rs_sdk::internal::abort_export(#export_func_name, &error)
+        };
+
+        let returns_value = matches!(
+            self.signature.output_type.as_ref().map(passing_style_of),
+            Some(PassingStyle::ByValue)
+        );
+        let on_error = match &self.original.sig.output {
+            syn::ReturnType::Type(_, ty) if returns_value && self.converts_export_errors() => {
+                quote::quote! {
+                    <#ty as marine_rs_sdk::FromExportError>::from_export_error(error)
+                }
+            }
+            syn::ReturnType::Default => quote::quote! {
+                marine_rs_sdk::internal::report_export_error(#export_func_name, &error)
+            },
+            // returned references point to lifted arguments, so they are lifted in the same scope
+            syn::ReturnType::Type(..) if !returns_value => {
+                return quote::quote! {
+                    if let Err(error) = #allocation_check {
+                        #abort
+                    }
+                    #prolog
+                    #return_expression #original_call;
+                };
+            }
+            syn::ReturnType::Type(..) => abort,
+        };
+
+        quote::quote! {
+            #return_expression match #allocation_check {
+                Ok(()) => {
+                    #prolog
+                    #original_call
+                }
+                Err(error) => #on_error,
+            };
+        }
+    }
+}
+
+fn generate_caller_check(attrs: &ast_types::AstFnAttrs) -> TokenStream {
+    let owner = attrs.only_owner;
+    let host = attrs.only_host;
+    let peers = attrs.allowed_peers.iter().flatten();
+
+    quote::quote! {
+        marine_rs_sdk::internal::check_caller(
+            &call_parameters,
+            &marine_rs_sdk::internal::CallerPolicy {
+                owner: #owner,
+                host: #host,
+                peers: &[#(#peers),*],
+            },
+        )
+    }
+}
+
+fn generate_tetraplet_checks(
+    signature: &ast_types::AstFnSignature,
+    requirements: &[ast_types::AstTetrapletRequirement],
+) -> TokenStream {
+    let requirements = requirements.iter().map(|requirement| {
+        let arg_index = requirement.arg_index;
+        let arg_name = &signature.arguments[arg_index].name;
+        let peer_pk = option_to_tokens(&requirement.peer_pk);
+        let service_id = option_to_tokens(&requirement.service_id);
+        let function_name = option_to_tokens(&requirement.function_name);
+        let no_lens = requirement.no_lens;
+        let literal = requirement.literal;
+
+        quote::quote! {
+            marine_rs_sdk::internal::TetrapletRequirement {
+                arg_index: #arg_index,
+                arg_name: #arg_name,
+                peer_pk: #peer_pk,
+                service_id: #service_id,
+                function_name: #function_name,
+                no_lens: #no_lens,
+                literal: #literal,
+            }
+        }
+    });
+
+    quote::quote! {
+        marine_rs_sdk::internal::check_tetraplets(
+            &call_parameters,
+            &[#(#requirements),*],
+        )
+    }
+}
+
+/// Calls the original function only if checks are passed, otherwise converts the check error
+/// into the return value with FromExportError.
+fn generate_checked_call(
+    original_call: TokenStream,
+    checks: Vec<TokenStream>,
+    output_type: &syn::Type,
+) -> TokenStream {
+    let (first_check, other_checks) = checks
+        .split_first()
+        .expect("checked call should have at least one check");
+
+    quote::quote! {
+        match {
+            let call_parameters = marine_rs_sdk::get_cached_call_parameters();
+            #first_check #(.and_then(|()| #other_checks))*
+        } {
+            Ok(()) => #original_call,
+            Err(error) => <#output_type as marine_rs_sdk::FromExportError>::from_export_error(error),
+        }
+    }
+}
+
+fn option_to_tokens(value: &Option<String>) -> TokenStream {
+    match value {
+        Some(value) => quote::quote! { Some(#value) },
+        None => quote::quote! { None },
+    }
+}
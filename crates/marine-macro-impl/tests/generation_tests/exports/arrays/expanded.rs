@@ -7,65 +7,72 @@ pub fn inner_arrays_1(arg: Vec<Vec<Vec<Vec<u8>>>>) -> Vec<Vec<Vec<Vec<u8>>>> {
 #[doc(hidden)]
 #[allow(clippy::all)]
 pub unsafe fn __m_generated_wrapper_func_inner_arrays_1(arg_0: u32, arg_1: u32) {
-    unsafe fn __m_generated_vec_deserializer_0(offset: u32, size: u32) -> Vec<Vec<Vec<Vec<u8>>>> {
-        unsafe fn __m_generated_vec_deserializer_0_Vec_Vec_u8__(
-            offset: u32,
-            size: u32
-        ) -> Vec<Vec<Vec<u8>>> {
-            unsafe fn __m_generated_vec_deserializer_0_Vec_Vec_u8___Vec_u8_(
-                offset: u32,
-                size: u32
-            ) -> Vec<Vec<u8>> {
-                unsafe fn __m_generated_vec_deserializer_0_Vec_Vec_u8___Vec_u8__u8(
+    marine_rs_sdk::internal::enter_export("inner_arrays_1");
+    let result = match marine_rs_sdk::internal::check_allocations(&[(arg_0, arg_1, "arg", marine_rs_sdk::AllocationElementType::Pointer)]) {
+        Ok(()) => {
+            unsafe fn __m_generated_vec_deserializer_0(offset: u32, size: u32) -> Vec<Vec<Vec<Vec<u8>>>> {
+                unsafe fn __m_generated_vec_deserializer_0_Vec_Vec_u8__(
                     offset: u32,
                     size: u32
-                ) -> Vec<u8> {
-                    match size {
-                        0 => Vec::default(),
-                        _ => Vec::from_raw_parts(offset as _, size as _, size as _)
+                ) -> Vec<Vec<Vec<u8>>> {
+                    unsafe fn __m_generated_vec_deserializer_0_Vec_Vec_u8___Vec_u8_(
+                        offset: u32,
+                        size: u32
+                    ) -> Vec<Vec<u8>> {
+                        unsafe fn __m_generated_vec_deserializer_0_Vec_Vec_u8___Vec_u8__u8(
+                            offset: u32,
+                            size: u32
+                        ) -> Vec<u8> {
+                            match size {
+                                0 => Vec::default(),
+                                _ => Vec::from_raw_parts(offset as _, size as _, size as _)
+                            }
+                        }
+                        let vec_passing_size = 2;
+                        let mut arg: Vec<u32> =
+                            Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+                        let mut result = Vec::with_capacity(arg.len());
+                        let mut arg = arg.into_iter();
+                        while let Some(offset) = arg.next() {
+                            let size = arg.next().unwrap();
+                            let value = __m_generated_vec_deserializer_0_Vec_Vec_u8___Vec_u8__u8(
+                                offset as _,
+                                size as _
+                            );
+                            result.push(value);
+                        }
+                        result
                     }
+                    let vec_passing_size = 2;
+                    let mut arg: Vec<u32> =
+                        Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+                    let mut result = Vec::with_capacity(arg.len());
+                    let mut arg = arg.into_iter();
+                    while let Some(offset) = arg.next() {
+                        let size = arg.next().unwrap();
+                        let value =
+                            __m_generated_vec_deserializer_0_Vec_Vec_u8___Vec_u8_(offset as _, size as _);
+                        result.push(value);
+                    }
+                    result
                 }
                 let vec_passing_size = 2;
-                let mut arg: Vec<u32> =
-                    Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+                let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
                 let mut result = Vec::with_capacity(arg.len());
                 let mut arg = arg.into_iter();
                 while let Some(offset) = arg.next() {
                     let size = arg.next().unwrap();
-                    let value = __m_generated_vec_deserializer_0_Vec_Vec_u8___Vec_u8__u8(
-                        offset as _,
-                        size as _
-                    );
+                    let value = __m_generated_vec_deserializer_0_Vec_Vec_u8__(offset as _, size as _);
                     result.push(value);
                 }
                 result
             }
-            let vec_passing_size = 2;
-            let mut arg: Vec<u32> =
-                Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
-            let mut result = Vec::with_capacity(arg.len());
-            let mut arg = arg.into_iter();
-            while let Some(offset) = arg.next() {
-                let size = arg.next().unwrap();
-                let value =
-                    __m_generated_vec_deserializer_0_Vec_Vec_u8___Vec_u8_(offset as _, size as _);
-                result.push(value);
-            }
-            result
+            let converted_arg_0 = __m_generated_vec_deserializer_0(arg_0 as _, arg_1 as _);
+            inner_arrays_1(converted_arg_0)
         }
-        let vec_passing_size = 2;
-        let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
-        let mut result = Vec::with_capacity(arg.len());
-        let mut arg = arg.into_iter();
-        while let Some(offset) = arg.next() {
-            let size = arg.next().unwrap();
-            let value = __m_generated_vec_deserializer_0_Vec_Vec_u8__(offset as _, size as _);
-            result.push(value);
-        }
-        result
-    }
-    let converted_arg_0 = __m_generated_vec_deserializer_0(arg_0 as _, arg_1 as _);
-    let result = inner_arrays_1(converted_arg_0);
+        Err(error) => marine_rs_sdk::internal::abort_export("inner_arrays_1", &error),
+    };
+    marine_rs_sdk::internal::exit_export();
     unsafe fn __m_generated_vec_serializer(arg: &Vec<Vec<Vec<Vec<u8>>>>) -> (u32, u32) {
         unsafe fn __m_generated_vec_serializer_Vec_Vec_u8__(
             arg: &Vec<Vec<Vec<u8>>>
@@ -35,41 +35,48 @@ pub unsafe fn __m_generated_wrapper_func_all_types(
     arg_12: u32,
     arg_13: u32
 ) {
-    let converted_arg_0 = arg_0 as _;
-    let converted_arg_1 = arg_1 as _;
-    let converted_arg_2 = arg_2 as _;
-    let converted_arg_3 = arg_3 as _;
-    let converted_arg_4 = arg_4 as _;
-    let converted_arg_5 = arg_5 as _;
-    let converted_arg_6 = arg_6 as _;
-    let converted_arg_7 = arg_7 as _;
-    let converted_arg_8 = arg_8 as _;
-    let converted_arg_9 = arg_9 as _;
-    let converted_arg_10 = match arg_11 {
-        0 => String::default(),
-        _ => String::from_raw_parts(arg_10 as _, arg_11 as _, arg_11 as _)
-    };
-    unsafe fn __m_generated_vec_deserializer_12(offset: u32, size: u32) -> Vec<u8> {
-        match size {
-            0 => Vec::default(),
-            _ => Vec::from_raw_parts(offset as _, size as _, size as _)
+    marine_rs_sdk::internal::enter_export("all_types");
+    let result = match marine_rs_sdk::internal::check_allocations(&[(arg_10, arg_11, "arg_10", marine_rs_sdk::AllocationElementType::U8), (arg_12, arg_13, "arg_11", marine_rs_sdk::AllocationElementType::U8)]) {
+        Ok(()) => {
+            let converted_arg_0 = arg_0 as _;
+            let converted_arg_1 = arg_1 as _;
+            let converted_arg_2 = arg_2 as _;
+            let converted_arg_3 = arg_3 as _;
+            let converted_arg_4 = arg_4 as _;
+            let converted_arg_5 = arg_5 as _;
+            let converted_arg_6 = arg_6 as _;
+            let converted_arg_7 = arg_7 as _;
+            let converted_arg_8 = arg_8 as _;
+            let converted_arg_9 = arg_9 as _;
+            let converted_arg_10 = match arg_11 {
+                0 => String::default(),
+                _ => String::from_raw_parts(arg_10 as _, arg_11 as _, arg_11 as _)
+            };
+            unsafe fn __m_generated_vec_deserializer_12(offset: u32, size: u32) -> Vec<u8> {
+                match size {
+                    0 => Vec::default(),
+                    _ => Vec::from_raw_parts(offset as _, size as _, size as _)
+                }
+            }
+            let converted_arg_12 = __m_generated_vec_deserializer_12(arg_12 as _, arg_13 as _);
+            all_types(
+                converted_arg_0,
+                converted_arg_1,
+                converted_arg_2,
+                converted_arg_3,
+                converted_arg_4,
+                converted_arg_5,
+                converted_arg_6,
+                converted_arg_7,
+                converted_arg_8,
+                converted_arg_9,
+                converted_arg_10,
+                converted_arg_12
+            )
         }
-    }
-    let converted_arg_12 = __m_generated_vec_deserializer_12(arg_12 as _, arg_13 as _);
-    let result = all_types(
-        converted_arg_0,
-        converted_arg_1,
-        converted_arg_2,
-        converted_arg_3,
-        converted_arg_4,
-        converted_arg_5,
-        converted_arg_6,
-        converted_arg_7,
-        converted_arg_8,
-        converted_arg_9,
-        converted_arg_10,
-        converted_arg_12
-    );
+        Err(error) => marine_rs_sdk::internal::abort_export("all_types", &error),
+    };
+    marine_rs_sdk::internal::exit_export();
     unsafe fn __m_generated_vec_serializer(arg: &Vec<u8>) -> (u32, u32) {
         (arg.as_ptr() as _, arg.len() as _)
     }
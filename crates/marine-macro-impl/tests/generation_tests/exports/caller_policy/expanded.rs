@@ -0,0 +1,57 @@
+pub fn remove_user(user: String) -> MountedBinaryResult {
+    unimplemented!()
+}
+#[cfg(target_arch = "wasm32")]
+#[export_name = "remove_user"]
+#[no_mangle]
+#[doc(hidden)]
+#[allow(clippy::all)]
+pub unsafe fn __m_generated_wrapper_func_remove_user(arg_0: u32, arg_1: u32) {
+    marine_rs_sdk::internal::enter_export("remove_user");
+    let result = match marine_rs_sdk::internal::check_allocations(&[(arg_0, arg_1, "user", marine_rs_sdk::AllocationElementType::U8)]) {
+        Ok(()) => {
+            let converted_arg_0 = match arg_1 {
+                0 => String::default(),
+                _ => String::from_raw_parts(arg_0 as _, arg_1 as _, arg_1 as _)
+            };
+            match {
+                let call_parameters = marine_rs_sdk::get_cached_call_parameters();
+                marine_rs_sdk::internal::check_caller(
+                    &call_parameters,
+                    &marine_rs_sdk::internal::CallerPolicy {
+                        owner: true,
+                        host: false,
+                        peers: &["peer_1", "peer_2"],
+                    },
+                )
+                .and_then(|()| marine_rs_sdk::internal::check_tetraplets(
+                        &call_parameters,
+                        &[marine_rs_sdk::internal::TetrapletRequirement {
+                            arg_index: 0usize,
+                            arg_name: "user",
+                            peer_pk: None,
+                            service_id: None,
+                            function_name: None,
+                            no_lens: false,
+                            literal: true,
+                        }],
+                    ))
+            } {
+                Ok(()) => remove_user(converted_arg_0),
+                Err(error) => <MountedBinaryResult as marine_rs_sdk::FromExportError>::from_export_error(error),
+            }
+        }
+        Err(error) => <MountedBinaryResult as marine_rs_sdk::FromExportError>::from_export_error(error),
+    };
+    marine_rs_sdk::internal::exit_export();
+    let result_ptr = result.__m_generated_serialize();
+    marine_rs_sdk::internal::set_result_ptr(result_ptr as _);
+    marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+}
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+#[allow(clippy::all)]
+#[link_section = "__m_generated_section__remove_user"]
+pub static __m_generated_static_global_remove_user: [u8; 252usize] = {
+    * b"{\"ast_type\":\"Function\",\"signature\":{\"name\":\"remove_user\",\"arguments\":[{\"name\":\"user\",\"ty\":{\"Utf8String\":\"ByValue\"}}],\"output_types\":[{\"Record\":[\"MountedBinaryResult\",\"ByValue\"]}]},\"caller_policy\":{\"owner\":true,\"host\":false,\"peers\":[\"peer_1\",\"peer_2\"]}}"
+};
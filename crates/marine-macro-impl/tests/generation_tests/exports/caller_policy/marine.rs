@@ -0,0 +1,3 @@
+pub fn remove_user(#[marine(require_tetraplet(literal))] user: String) -> MountedBinaryResult {
+    unimplemented!()
+}
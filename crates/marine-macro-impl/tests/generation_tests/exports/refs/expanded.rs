@@ -7,6 +7,10 @@ pub fn test_array_refs(arg: &Vec<Vec<String>>) -> &Vec<Vec<Vec<Vec<String>>>> {
 #[doc(hidden)]
 #[allow(clippy::all)]
 pub unsafe fn __m_generated_wrapper_func_test_array_refs(arg_0: u32, arg_1: u32) {
+    marine_rs_sdk::internal::enter_export("test_array_refs");
+    if let Err(error) = marine_rs_sdk::internal::check_allocations(&[(arg_0, arg_1, "arg", marine_rs_sdk::AllocationElementType::Pointer)]) {
+        marine_rs_sdk::internal::abort_export("test_array_refs", &error)
+    }
     unsafe fn __m_generated_vec_deserializer_0(offset: u32, size: u32) -> Vec<Vec<String>> {
         unsafe fn __m_generated_vec_deserializer_0_String(offset: u32, size: u32) -> Vec<String> {
             let vec_passing_size = 2;
@@ -37,6 +41,7 @@ pub unsafe fn __m_generated_wrapper_func_test_array_refs(arg_0: u32, arg_1: u32)
     }
     let converted_arg_0 = __m_generated_vec_deserializer_0(arg_0 as _, arg_1 as _);
     let result = test_array_refs(&converted_arg_0);
+    marine_rs_sdk::internal::exit_export();
     unsafe fn __m_generated_vec_serializer(arg: &Vec<Vec<Vec<Vec<String>>>>) -> (u32, u32) {
         unsafe fn __m_generated_vec_serializer_Vec_Vec_String__(
             arg: &Vec<Vec<Vec<String>>>
@@ -0,0 +1,64 @@
+pub fn set_value(
+    key: String,
+    value: u64,
+) -> MountedBinaryResult {
+    unimplemented!()
+}
+#[cfg(target_arch = "wasm32")]
+#[export_name = "set_value"]
+#[no_mangle]
+#[doc(hidden)]
+#[allow(clippy::all)]
+pub unsafe fn __m_generated_wrapper_func_set_value(arg_0: u32, arg_1: u32, arg_2: u64) {
+    marine_rs_sdk::internal::enter_export("set_value");
+    let result = match marine_rs_sdk::internal::check_allocations(&[(arg_0, arg_1, "key", marine_rs_sdk::AllocationElementType::U8)]) {
+        Ok(()) => {
+            let converted_arg_0 = match arg_1 {
+                0 => String::default(),
+                _ => String::from_raw_parts(arg_0 as _, arg_1 as _, arg_1 as _)
+            };
+            let converted_arg_2 = arg_2 as _;
+            match {
+                let call_parameters = marine_rs_sdk::get_cached_call_parameters();
+                marine_rs_sdk::internal::check_tetraplets(
+                    &call_parameters,
+                    &[
+                        marine_rs_sdk::internal::TetrapletRequirement {
+                            arg_index: 0usize,
+                            arg_name: "key",
+                            peer_pk: Some("peer"),
+                            service_id: Some("service"),
+                            function_name: None,
+                            no_lens: true,
+                            literal: false,
+                        },
+                        marine_rs_sdk::internal::TetrapletRequirement {
+                            arg_index: 1usize,
+                            arg_name: "value",
+                            peer_pk: None,
+                            service_id: None,
+                            function_name: None,
+                            no_lens: false,
+                            literal: true,
+                        }
+                    ],
+                )
+            } {
+                Ok(()) => set_value(converted_arg_0, converted_arg_2),
+                Err(error) => <MountedBinaryResult as marine_rs_sdk::FromExportError>::from_export_error(error),
+            }
+        }
+        Err(error) => <MountedBinaryResult as marine_rs_sdk::FromExportError>::from_export_error(error),
+    };
+    marine_rs_sdk::internal::exit_export();
+    let result_ptr = result.__m_generated_serialize();
+    marine_rs_sdk::internal::set_result_ptr(result_ptr as _);
+    marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+}
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+#[allow(clippy::all)]
+#[link_section = "__m_generated_section__set_value"]
+pub static __m_generated_static_global_set_value: [u8; 217usize] = {
+    * b"{\"ast_type\":\"Function\",\"signature\":{\"name\":\"set_value\",\"arguments\":[{\"name\":\"key\",\"ty\":{\"Utf8String\":\"ByValue\"}},{\"name\":\"value\",\"ty\":{\"U64\":\"ByValue\"}}],\"output_types\":[{\"Record\":[\"MountedBinaryResult\",\"ByValue\"]}]}}"
+};
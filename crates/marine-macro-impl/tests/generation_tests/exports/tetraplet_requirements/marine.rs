@@ -0,0 +1,6 @@
+pub fn set_value(
+    #[marine(require_tetraplet(peer_id = "peer", service_id = "service", no_lens))] key: String,
+    #[marine(require_tetraplet(literal))] value: u64,
+) -> MountedBinaryResult {
+    unimplemented!()
+}
@@ -0,0 +1,31 @@
+pub fn store_record(record: TestRecord, name: String) -> u64 {
+    unimplemented!()
+}
+#[cfg(target_arch = "wasm32")]
+#[export_name = "store_record"]
+#[no_mangle]
+#[doc(hidden)]
+#[allow(clippy::all)]
+pub unsafe fn __m_generated_wrapper_func_store_record(arg_0: u32, arg_1: u32, arg_2: u32) -> u64 {
+    marine_rs_sdk::internal::enter_export("store_record");
+    let result = match marine_rs_sdk::internal::check_allocations(&[(arg_1, arg_2, "name", marine_rs_sdk::AllocationElementType::U8)]) {
+        Ok(()) => {
+            let converted_arg_0 = TestRecord::__m_generated_deserialize(arg_0 as _);
+            let converted_arg_1 = match arg_2 {
+                0 => String::default(),
+                _ => String::from_raw_parts(arg_1 as _, arg_2 as _, arg_2 as _)
+            };
+            store_record(converted_arg_0, converted_arg_1)
+        }
+        Err(error) => marine_rs_sdk::internal::abort_export("store_record", &error),
+    };
+    marine_rs_sdk::internal::exit_export();
+    return result as _;
+}
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+#[allow(clippy::all)]
+#[link_section = "__m_generated_section__store_record"]
+pub static __m_generated_static_global_store_record: [u8; 213usize] = {
+    * b"{\"ast_type\":\"Function\",\"signature\":{\"name\":\"store_record\",\"arguments\":[{\"name\":\"record\",\"ty\":{\"Record\":[\"TestRecord\",\"ByValue\"]}},{\"name\":\"name\",\"ty\":{\"Utf8String\":\"ByValue\"}}],\"output_types\":[{\"U64\":\"ByValue\"}]}}"
+};
@@ -0,0 +1,3 @@
+pub fn store_record(record: TestRecord, name: String) -> u64 {
+    unimplemented!()
+}
@@ -7,71 +7,78 @@ pub fn inner_arrays_2(arg: Vec<Vec<Vec<Vec<TestRecord>>>>) -> Vec<Vec<Vec<Vec<Te
 #[doc(hidden)]
 #[allow(clippy::all)]
 pub unsafe fn __m_generated_wrapper_func_inner_arrays_2(arg_0: u32, arg_1: u32) {
-    unsafe fn __m_generated_vec_deserializer_0(
-        offset: u32,
-        size: u32
-    ) -> Vec<Vec<Vec<Vec<TestRecord>>>> {
-        unsafe fn __m_generated_vec_deserializer_0_Vec_Vec_TestRecord__(
-            offset: u32,
-            size: u32
-        ) -> Vec<Vec<Vec<TestRecord>>> {
-            unsafe fn __m_generated_vec_deserializer_0_Vec_Vec_TestRecord___Vec_TestRecord_(
+    marine_rs_sdk::internal::enter_export("inner_arrays_2");
+    let result = match marine_rs_sdk::internal::check_allocations(&[(arg_0, arg_1, "arg", marine_rs_sdk::AllocationElementType::Pointer)]) {
+        Ok(()) => {
+            unsafe fn __m_generated_vec_deserializer_0(
                 offset: u32,
                 size: u32
-            ) -> Vec<Vec<TestRecord>> {
-                unsafe fn __m_generated_vec_deserializer_0_Vec_Vec_TestRecord___Vec_TestRecord__TestRecord(
+            ) -> Vec<Vec<Vec<Vec<TestRecord>>>> {
+                unsafe fn __m_generated_vec_deserializer_0_Vec_Vec_TestRecord__(
                     offset: u32,
                     size: u32
-                ) -> Vec<TestRecord> {
-                    let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, size as _, size as _);
+                ) -> Vec<Vec<Vec<TestRecord>>> {
+                    unsafe fn __m_generated_vec_deserializer_0_Vec_Vec_TestRecord___Vec_TestRecord_(
+                        offset: u32,
+                        size: u32
+                    ) -> Vec<Vec<TestRecord>> {
+                        unsafe fn __m_generated_vec_deserializer_0_Vec_Vec_TestRecord___Vec_TestRecord__TestRecord(
+                            offset: u32,
+                            size: u32
+                        ) -> Vec<TestRecord> {
+                            let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, size as _, size as _);
+                            let mut result = Vec::with_capacity(arg.len());
+                            for offset in arg {
+                                let value = TestRecord::__m_generated_deserialize(offset as _);
+                                result.push(value);
+                            }
+                            result
+                        }
+                        let vec_passing_size = 2;
+                        let mut arg: Vec<u32> =
+                            Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+                        let mut result = Vec::with_capacity(arg.len());
+                        let mut arg = arg.into_iter();
+                        while let Some(offset) = arg.next() {
+                            let size = arg.next().unwrap();
+                            let value = __m_generated_vec_deserializer_0_Vec_Vec_TestRecord___Vec_TestRecord__TestRecord ( offset as _ , size as _ ) ;
+                            result.push(value);
+                        }
+                        result
+                    }
+                    let vec_passing_size = 2;
+                    let mut arg: Vec<u32> =
+                        Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
                     let mut result = Vec::with_capacity(arg.len());
-                    for offset in arg {
-                        let value = TestRecord::__m_generated_deserialize(offset as _);
+                    let mut arg = arg.into_iter();
+                    while let Some(offset) = arg.next() {
+                        let size = arg.next().unwrap();
+                        let value = __m_generated_vec_deserializer_0_Vec_Vec_TestRecord___Vec_TestRecord_(
+                            offset as _,
+                            size as _
+                        );
                         result.push(value);
                     }
                     result
                 }
                 let vec_passing_size = 2;
-                let mut arg: Vec<u32> =
-                    Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+                let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
                 let mut result = Vec::with_capacity(arg.len());
                 let mut arg = arg.into_iter();
                 while let Some(offset) = arg.next() {
                     let size = arg.next().unwrap();
-                    let value = __m_generated_vec_deserializer_0_Vec_Vec_TestRecord___Vec_TestRecord__TestRecord ( offset as _ , size as _ ) ;
+                    let value =
+                        __m_generated_vec_deserializer_0_Vec_Vec_TestRecord__(offset as _, size as _);
                     result.push(value);
                 }
                 result
             }
-            let vec_passing_size = 2;
-            let mut arg: Vec<u32> =
-                Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
-            let mut result = Vec::with_capacity(arg.len());
-            let mut arg = arg.into_iter();
-            while let Some(offset) = arg.next() {
-                let size = arg.next().unwrap();
-                let value = __m_generated_vec_deserializer_0_Vec_Vec_TestRecord___Vec_TestRecord_(
-                    offset as _,
-                    size as _
-                );
-                result.push(value);
-            }
-            result
+            let converted_arg_0 = __m_generated_vec_deserializer_0(arg_0 as _, arg_1 as _);
+            inner_arrays_2(converted_arg_0)
         }
-        let vec_passing_size = 2;
-        let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
-        let mut result = Vec::with_capacity(arg.len());
-        let mut arg = arg.into_iter();
-        while let Some(offset) = arg.next() {
-            let size = arg.next().unwrap();
-            let value =
-                __m_generated_vec_deserializer_0_Vec_Vec_TestRecord__(offset as _, size as _);
-            result.push(value);
-        }
-        result
-    }
-    let converted_arg_0 = __m_generated_vec_deserializer_0(arg_0 as _, arg_1 as _);
-    let result = inner_arrays_2(converted_arg_0);
+        Err(error) => marine_rs_sdk::internal::abort_export("inner_arrays_2", &error),
+    };
+    marine_rs_sdk::internal::exit_export();
     unsafe fn __m_generated_vec_serializer(arg: &Vec<Vec<Vec<Vec<TestRecord>>>>) -> (u32, u32) {
         unsafe fn __m_generated_vec_serializer_Vec_Vec_TestRecord__(
             arg: &Vec<Vec<Vec<TestRecord>>>
@@ -1,6 +1,7 @@
 mod utils;
 
 use utils::test_marine_token_streams;
+use utils::test_marine_token_streams_with_attrs;
 
 #[test]
 fn exports_arrays() {
@@ -10,6 +11,23 @@ fn exports_arrays() {
     ));
 }
 
+#[test]
+fn exports_tetraplet_requirements() {
+    assert!(test_marine_token_streams(
+        "tests/generation_tests/exports/tetraplet_requirements/marine.rs",
+        "tests/generation_tests/exports/tetraplet_requirements/expanded.rs",
+    ));
+}
+
+#[test]
+fn exports_caller_policy() {
+    assert!(test_marine_token_streams_with_attrs(
+        quote::quote! { only_owner, allow_peers = ["peer_1", "peer_2"] },
+        "tests/generation_tests/exports/caller_policy/marine.rs",
+        "tests/generation_tests/exports/caller_policy/expanded.rs",
+    ));
+}
+
 #[test]
 fn exports_basic_types() {
     assert!(test_marine_token_streams(
@@ -34,6 +52,14 @@ fn records_call_parameters() {
     ));
 }
 
+#[test]
+fn records_pass_by_value() {
+    assert!(test_marine_token_streams(
+        "tests/generation_tests/records/pass_by_value/marine.rs",
+        "tests/generation_tests/records/pass_by_value/expanded.rs",
+    ));
+}
+
 #[test]
 fn records_use_as_type() {
     assert!(test_marine_token_streams(
@@ -49,6 +49,42 @@
 //! }
 //!
 //! ```
+//!
+//! Required origins of export function arguments could be specified with
+//! `#[marine(require_tetraplet(...))]`, they are checked against tetraplets from call parameters
+//! before calling the function. On mismatch the error is converted into an error value of the return
+//! type, which should implement `marine_rs_sdk::FromExportError`, so such functions should return
+//! a value:
+//!
+//! ```ignore
+//! #[marine]
+//! pub fn set_admin(
+//!     #[marine(require_tetraplet(peer_id = "12D3KooW...", service_id = "auth", no_lens))]
+//!     admin: String,
+//!     #[marine(require_tetraplet(literal))] comment: String,
+//! ) -> MountedBinaryResult {
+//!     // ...
+//! }
+//! ```
+//!
+//! Callers of an export function could be restricted by `#[marine(only_owner)]`,
+//! `#[marine(only_host)]` and `#[marine(allow_peers = [...])]`, the init peer of the particle is
+//! allowed if it's the service creator, the host or one of the listed peers respectively. If several
+//! of them are specified, the caller should match any of them. A rejected call is converted into
+//! the return value the same way, and this policy is also recorded in the function metadata:
+//!
+//! ```ignore
+//! #[marine(only_owner, allow_peers = ["12D3KooW..."])]
+//! pub fn clear_storage() -> MountedBinaryResult {
+//!     // ...
+//! }
+//! ```
+//!
+//! Before lifting strings and vectors passed to an export function, the generated code checks
+//! that host managed to allocate them. If it didn't, the error is converted into the return value
+//! for functions using the checks above, and is logged for functions without a return value.
+//! Otherwise, there is no value to return, so the error is logged and the call is aborted
+//! with a trap. Records passed by value aren't checked, because they are passed by a pointer only.
 
 #![doc(html_root_url = "https://docs.rs/marine-macro/0.7.1")] // x-release-please-version
 #![deny(
@@ -62,13 +98,13 @@
 #![warn(rust_2018_idioms)]
 #![recursion_limit = "1024"]
 
-use marine_macro_impl::marine as marine_impl;
+use marine_macro_impl::marine_with_attrs as marine_impl;
 use proc_macro::TokenStream;
 
 #[proc_macro_attribute]
-pub fn marine(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn marine(attr: TokenStream, input: TokenStream) -> TokenStream {
     // into converts proc_macro::TokenStream to proc_macro2::TokenStream
-    match marine_impl(input.into()) {
+    match marine_impl(attr.into(), input.into()) {
         Ok(v) => v,
         // converts syn:error to proc_macro2::TokenStream
         Err(e) => e.to_compile_error(),
@@ -80,9 +116,9 @@ pub fn marine(_attr: TokenStream, input: TokenStream) -> TokenStream {
 // deprecated macro for backwards compatibility
 #[deprecated(since = "0.6.2", note = "please use the #[marine] macro instead")]
 #[proc_macro_attribute]
-pub fn fce(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn fce(attr: TokenStream, input: TokenStream) -> TokenStream {
     // into converts proc_macro::TokenStream to proc_macro2::TokenStream
-    match marine_impl(input.into()) {
+    match marine_impl(attr.into(), input.into()) {
         Ok(v) => v,
         // converts syn:error to proc_macro2::TokenStream
         Err(e) => e.to_compile_error(),
@@ -0,0 +1,304 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Checks of export function arguments and callers performed by the code generated by
+//! `#[marine]` before calling the original function.
+//!
+//! Allocation of complex arguments is checked before they are lifted: host passes a null pointer
+//! with a non-zero size, if `allocate` failed, and the pointer must be aligned for elements
+//! of the argument.
+
+use crate::AllocationElementType;
+use crate::CallParameters;
+use crate::ExportError;
+use crate::SecurityTetraplet;
+
+/// Peers allowed to call a function, specified by `#[marine(only_owner)]`, `#[marine(only_host)]`
+/// and `#[marine(allow_peers = [...])]`. The caller is allowed if it matches any of them.
+#[doc(hidden)]
+#[derive(Debug, Clone, Default)]
+pub struct CallerPolicy {
+    pub owner: bool,
+    pub host: bool,
+    pub peers: &'static [&'static str],
+}
+
+/// Checks that the init peer of the current particle is allowed by the policy.
+#[doc(hidden)]
+pub fn check_caller(
+    call_parameters: &CallParameters,
+    policy: &CallerPolicy,
+) -> Result<(), ExportError> {
+    let init_peer_id = &call_parameters.particle.init_peer_id;
+    let allowed = (policy.owner && *init_peer_id == call_parameters.service_creator_peer_id)
+        || (policy.host && *init_peer_id == call_parameters.host_id)
+        || policy.peers.contains(&init_peer_id.as_str());
+
+    if allowed {
+        Ok(())
+    } else {
+        Err(ExportError::CallerNotAllowed {
+            init_peer_id: init_peer_id.clone(),
+        })
+    }
+}
+
+/// A required origin of an argument, specified by `#[marine(require_tetraplet(...))]`.
+#[doc(hidden)]
+#[derive(Debug, Clone, Default)]
+pub struct TetrapletRequirement {
+    pub arg_index: usize,
+    pub arg_name: &'static str,
+    pub peer_pk: Option<&'static str>,
+    pub service_id: Option<&'static str>,
+    pub function_name: Option<&'static str>,
+    pub no_lens: bool,
+    pub literal: bool,
+}
+
+/// Checks that all tetraplets of the required arguments satisfy requirements.
+#[doc(hidden)]
+pub fn check_tetraplets(
+    call_parameters: &CallParameters,
+    requirements: &[TetrapletRequirement],
+) -> Result<(), ExportError> {
+    for requirement in requirements {
+        let tetraplets = call_parameters
+            .tetraplets
+            .get(requirement.arg_index)
+            .filter(|tetraplets| !tetraplets.is_empty())
+            .ok_or_else(|| requirement.mismatch("there are no tetraplets for it".to_string()))?;
+
+        for tetraplet in tetraplets {
+            requirement.check(tetraplet, &call_parameters.particle.init_peer_id)?;
+        }
+    }
+
+    Ok(())
+}
+
+impl TetrapletRequirement {
+    fn check(&self, tetraplet: &SecurityTetraplet, init_peer_id: &str) -> Result<(), ExportError> {
+        if self.literal && (!tetraplet.is_literal() || tetraplet.peer_pk != init_peer_id) {
+            return Err(self.mismatch(format!(
+                "it should be a literal from the init peer {}, but its tetraplet is {}",
+                init_peer_id, tetraplet
+            )));
+        }
+
+        let expected_fields = [
+            ("peer_pk", self.peer_pk, &tetraplet.peer_pk),
+            ("service_id", self.service_id, &tetraplet.service_id),
+            (
+                "function_name",
+                self.function_name,
+                &tetraplet.function_name,
+            ),
+        ];
+        for (field_name, expected, actual) in expected_fields {
+            match expected {
+                Some(expected) if expected != actual => {
+                    return Err(self.mismatch(format!(
+                        "its {} should be {}, but its tetraplet is {}",
+                        field_name, expected, tetraplet
+                    )))
+                }
+                _ => {}
+            }
+        }
+
+        if self.no_lens && tetraplet.has_lens() {
+            return Err(self.mismatch(format!(
+                "no lens should be applied to it, but its tetraplet is {}",
+                tetraplet
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn mismatch(&self, reason: String) -> ExportError {
+        ExportError::TetrapletMismatch {
+            argument: self.arg_name.to_string(),
+            reason,
+        }
+    }
+}
+
+/// Checks that memory for all (pointer, size, name, element type) complex arguments
+/// was allocated and is aligned for their elements.
+#[doc(hidden)]
+pub fn check_allocations(
+    arguments: &[(u32, u32, &'static str, AllocationElementType)],
+) -> Result<(), ExportError> {
+    for &(ptr, size, name, element_type) in arguments {
+        if ptr == 0 && size != 0 {
+            return Err(ExportError::AllocationFailed {
+                argument: name.to_string(),
+                size: size as usize,
+            });
+        }
+
+        if ptr as usize % element_type.align() != 0 {
+            return Err(ExportError::MisalignedArgument {
+                argument: name.to_string(),
+                ptr,
+                align: element_type.align(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_allocations;
+    use super::check_caller;
+    use super::check_tetraplets;
+    use super::CallerPolicy;
+    use super::TetrapletRequirement;
+    use crate::CallParameters;
+    use crate::ExportError;
+    use crate::SecurityTetraplet;
+
+    fn call_parameters(tetraplets: Vec<Vec<SecurityTetraplet>>) -> CallParameters {
+        let mut call_parameters = CallParameters::default();
+        call_parameters.particle.init_peer_id = "init_peer".to_string();
+        call_parameters.tetraplets = tetraplets;
+        call_parameters
+    }
+
+    #[test]
+    fn allocations() {
+        use crate::AllocationElementType::*;
+
+        assert_eq!(
+            check_allocations(&[(8, 3, "a", U8), (0, 0, "b", Pointer)]),
+            Ok(())
+        );
+        assert_eq!(
+            check_allocations(&[(8, 3, "a", U8), (0, 5, "b", U8)]),
+            Err(ExportError::AllocationFailed {
+                argument: "b".to_string(),
+                size: 5,
+            })
+        );
+        assert_eq!(
+            check_allocations(&[(9, 3, "a", U8), (10, 2, "b", U64)]),
+            Err(ExportError::MisalignedArgument {
+                argument: "b".to_string(),
+                ptr: 10,
+                align: 8,
+            })
+        );
+    }
+
+    #[test]
+    fn caller_policy() {
+        let mut call_parameters = call_parameters(vec![]);
+        call_parameters.service_creator_peer_id = "owner".to_string();
+        call_parameters.host_id = "host".to_string();
+
+        let only_owner = CallerPolicy {
+            owner: true,
+            ..<_>::default()
+        };
+        let owner_or_host = CallerPolicy {
+            owner: true,
+            host: true,
+            ..<_>::default()
+        };
+        let allowed_peers = CallerPolicy {
+            peers: &["peer_1", "init_peer"],
+            ..<_>::default()
+        };
+
+        assert_eq!(
+            check_caller(&call_parameters, &only_owner),
+            Err(ExportError::CallerNotAllowed {
+                init_peer_id: "init_peer".to_string()
+            })
+        );
+        assert_eq!(check_caller(&call_parameters, &allowed_peers), Ok(()));
+
+        call_parameters.particle.init_peer_id = "host".to_string();
+        assert!(check_caller(&call_parameters, &only_owner).is_err());
+        assert_eq!(check_caller(&call_parameters, &owner_or_host), Ok(()));
+    }
+
+    #[test]
+    fn tetraplet_requirements_satisfied() {
+        let call_parameters = call_parameters(vec![
+            vec![SecurityTetraplet::literal_tetraplet("init_peer")],
+            vec![SecurityTetraplet::new("peer", "service", "function", "")],
+        ]);
+        let requirements = [
+            TetrapletRequirement {
+                arg_index: 0,
+                arg_name: "arg_0",
+                literal: true,
+                ..<_>::default()
+            },
+            TetrapletRequirement {
+                arg_index: 1,
+                arg_name: "arg_1",
+                peer_pk: Some("peer"),
+                service_id: Some("service"),
+                no_lens: true,
+                ..<_>::default()
+            },
+        ];
+
+        assert_eq!(check_tetraplets(&call_parameters, &requirements), Ok(()));
+    }
+
+    #[test]
+    fn tetraplet_requirements_violated() {
+        let call_parameters = call_parameters(vec![
+            vec![SecurityTetraplet::literal_tetraplet("other_peer")],
+            vec![SecurityTetraplet::new(
+                "peer", "service", "function", ".$.[0]",
+            )],
+        ]);
+        let literal = TetrapletRequirement {
+            arg_index: 0,
+            arg_name: "arg_0",
+            literal: true,
+            ..<_>::default()
+        };
+        let no_lens = TetrapletRequirement {
+            arg_index: 1,
+            arg_name: "arg_1",
+            function_name: Some("function"),
+            no_lens: true,
+            ..<_>::default()
+        };
+        let missing = TetrapletRequirement {
+            arg_index: 2,
+            arg_name: "arg_2",
+            ..<_>::default()
+        };
+
+        for requirement in [literal, no_lens, missing] {
+            let arg_name = requirement.arg_name;
+            let result = check_tetraplets(&call_parameters, &[requirement]);
+            assert!(
+                matches!(result, Err(ExportError::TetrapletMismatch { argument, .. }) if argument == arg_name)
+            );
+        }
+    }
+}
@@ -0,0 +1,133 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Errors of export functions that are converted into their return values
+//! by the code generated by `#[marine]`.
+//!
+//! These are failed checks of arguments marked with `#[marine(require_tetraplet(...))]`,
+//! callers not allowed by `#[marine(only_owner)]`, `#[marine(only_host)]` or
+//! `#[marine(allow_peers = [...])]` and arguments host failed to allocate.
+
+use crate::MountedBinaryResult;
+use crate::MountedBinaryStringResult;
+
+use std::fmt;
+
+/// Return code of a result created from an export error, see [`FromExportError`].
+/// It differs from exit, signal and host error codes of mounted binaries,
+/// so such results are classified as [`crate::MountedBinaryStatus::ExportError`].
+pub const EXPORT_ERROR_CODE: i32 = -1000;
+
+/// An error that prevented an export function from returning its own value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ExportError {
+    /// Tetraplets of the argument don't satisfy its `require_tetraplet` requirement.
+    TetrapletMismatch { argument: String, reason: String },
+
+    /// The init peer of the particle isn't allowed to call the function.
+    CallerNotAllowed { init_peer_id: String },
+
+    /// Host couldn't allocate memory for the argument, because the module is out of memory.
+    AllocationFailed { argument: String, size: usize },
+
+    /// Host passed the argument at an address that isn't aligned for its elements.
+    MisalignedArgument {
+        argument: String,
+        ptr: u32,
+        align: usize,
+    },
+}
+
+/// Converts an export error into a value of an export function return type.
+///
+/// ```ignore
+/// #[marine]
+/// pub struct Response {
+///     pub error: String,
+///     pub data: Vec<u8>,
+/// }
+///
+/// impl FromExportError for Response {
+///     fn from_export_error(error: ExportError) -> Self {
+///         Self {
+///             error: error.to_string(),
+///             data: vec![],
+///         }
+///     }
+/// }
+/// ```
+pub trait FromExportError {
+    fn from_export_error(error: ExportError) -> Self;
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::TetrapletMismatch { argument, reason } => {
+                write!(
+                    f,
+                    "argument '{}' has unexpected origin: {}",
+                    argument, reason
+                )
+            }
+            ExportError::CallerNotAllowed { init_peer_id } => {
+                write!(
+                    f,
+                    "peer {} isn't allowed to call this function",
+                    init_peer_id
+                )
+            }
+            ExportError::AllocationFailed { argument, size } => {
+                write!(
+                    f,
+                    "couldn't allocate argument '{}' of size {}: out of memory",
+                    argument, size
+                )
+            }
+            ExportError::MisalignedArgument {
+                argument,
+                ptr,
+                align,
+            } => {
+                write!(
+                    f,
+                    "argument '{}' at {} isn't aligned to {} bytes",
+                    argument, ptr, align
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl FromExportError for MountedBinaryResult {
+    fn from_export_error(error: ExportError) -> Self {
+        MountedBinaryResult::from_error(EXPORT_ERROR_CODE, error.to_string())
+    }
+}
+
+impl FromExportError for MountedBinaryStringResult {
+    fn from_export_error(error: ExportError) -> Self {
+        Self {
+            ret_code: EXPORT_ERROR_CODE,
+            error: error.to_string(),
+            stdout: String::new(),
+            stderr: String::new(),
+        }
+    }
+}
@@ -67,7 +67,18 @@
 )]
 #![warn(rust_2018_idioms)]
 
+// checks are called only by the generated code
+#[cfg_attr(
+    not(all(feature = "marine-abi", target_arch = "wasm32")),
+    allow(dead_code)
+)]
+mod export_checks;
+mod export_error;
+#[cfg(feature = "memory-stats")]
+mod memory_stats;
 mod mounted_binary;
+#[cfg(feature = "runtime-log-level")]
+mod runtime_log_level;
 
 #[allow(unused_extern_crates)]
 // sdk is used inside CallParameters and MountedBinaryResult glue code
@@ -79,18 +90,108 @@ pub use marine_macro::fce;
 pub use marine_call_parameters::CallParameters;
 pub use marine_call_parameters::ParticleParameters;
 pub use marine_call_parameters::SecurityTetraplet;
+pub use marine_call_parameters::Lens;
+pub use marine_call_parameters::LensParseError;
+pub use marine_call_parameters::LensSegment;
 pub use marine_call_parameters::get_call_parameters;
+pub use marine_call_parameters::get_cached_call_parameters;
+pub use marine_call_parameters::reset_call_parameters_cache;
+pub use marine_call_parameters::get_host_id;
+pub use marine_call_parameters::get_init_peer_id;
+pub use marine_call_parameters::get_particle_id;
+pub use marine_call_parameters::get_service_creator_peer_id;
+pub use marine_call_parameters::get_service_id;
+pub use marine_call_parameters::get_tetraplets;
+pub use marine_call_parameters::get_worker_id;
+#[cfg(any(
+    feature = "host-api-v4",
+    not(all(feature = "marine-abi", target_arch = "wasm32"))
+))]
+pub use marine_call_parameters::get_current_time;
+#[cfg(all(
+    feature = "rkyv",
+    any(
+        feature = "host-api-v4",
+        not(all(feature = "marine-abi", target_arch = "wasm32"))
+    )
+))]
+pub use marine_call_parameters::get_archived_call_parameters;
+#[cfg(feature = "rkyv")]
+pub use marine_call_parameters::ArchivedCallParameters;
+#[cfg(feature = "rkyv")]
+pub use marine_call_parameters::ArchivedCallParametersBuffer;
+#[cfg(feature = "rkyv")]
+pub use marine_call_parameters::ArchivedCallParametersError;
+#[cfg(feature = "crypto")]
+pub use marine_call_parameters::ParticleSignatureError;
+#[cfg(feature = "crypto")]
+pub use marine_call_parameters::ed25519_public_key;
 
 #[cfg(feature = "logger")]
 pub use marine_rs_sdk_main::WasmLoggerBuilder;
 #[cfg(feature = "logger")]
 pub use marine_rs_sdk_main::TargetMap;
+#[cfg(feature = "logger")]
+pub use marine_rs_sdk_main::set_log_level;
+#[cfg(feature = "tracing")]
+pub use marine_rs_sdk_main::WasmTracingLayer;
+
+pub use marine_rs_sdk_main::HOST_API_NAMESPACE;
+pub use marine_rs_sdk_main::HOST_API_VERSION;
+
+#[cfg(feature = "arena-allocator")]
+pub use marine_rs_sdk_main::ArenaAllocator;
+#[cfg(feature = "arena-allocator")]
+pub use marine_rs_sdk_main::ArenaStats;
+#[cfg(all(
+    feature = "arena-allocator",
+    feature = "marine-abi",
+    target_arch = "wasm32"
+))]
+pub use marine_rs_sdk_main::arena_stats;
 
+#[cfg(feature = "memory-stats")]
+pub use memory_stats::MemoryStats;
+#[cfg(all(
+    feature = "memory-stats",
+    feature = "marine-abi",
+    target_arch = "wasm32"
+))]
+pub use memory_stats::memory_stats;
+
+pub use export_error::ExportError;
+pub use export_error::FromExportError;
+pub use export_error::EXPORT_ERROR_CODE;
+
+pub use mounted_binary::MountedBinaryCommand;
+pub use mounted_binary::MountedBinaryError;
 pub use mounted_binary::MountedBinaryResult;
+pub use mounted_binary::NonUtf8Output;
+#[cfg(feature = "mounted-binary-stream")]
+pub use mounted_binary::MountedBinaryProcess;
+#[cfg(feature = "mounted-binary-stream")]
+pub use mounted_binary::MountedBinaryReader;
+#[cfg(feature = "mounted-binary-stream")]
+pub use mounted_binary::OutputLimits;
+#[cfg(feature = "mounted-binary-stream")]
+pub use mounted_binary::OutputStream;
+#[cfg(feature = "mounted-binary-stream")]
+pub use mounted_binary::DEFAULT_OUTPUT_LIMIT;
 pub use mounted_binary::MountedBinaryStringResult;
 pub use mounted_binary::SUCCESS_CODE as BINARY_SUCCESS_CODE;
+pub use mounted_binary::HostErrorKind as BinaryHostErrorKind;
+pub use mounted_binary::MountedBinaryStatus;
+pub use mounted_binary::HOST_ERROR_CODE as BINARY_HOST_ERROR_CODE;
+pub use mounted_binary::INVALID_ARGUMENT_CODE as BINARY_INVALID_ARGUMENT_CODE;
+pub use mounted_binary::NOT_FOUND_CODE as BINARY_NOT_FOUND_CODE;
+pub use mounted_binary::OUTPUT_LIMIT_EXCEEDED_CODE as BINARY_OUTPUT_LIMIT_EXCEEDED_CODE;
+pub use mounted_binary::PERMISSION_DENIED_CODE as BINARY_PERMISSION_DENIED_CODE;
+pub use mounted_binary::SIGNAL_CODE_BASE as BINARY_SIGNAL_CODE_BASE;
+pub use mounted_binary::SPAWN_FAILED_CODE as BINARY_SPAWN_FAILED_CODE;
+pub use mounted_binary::TIMEOUT_CODE as BINARY_TIMEOUT_CODE;
 
 pub use marine_rs_sdk_main::module_manifest;
+pub use marine_rs_sdk_main::AllocationElementType;
 
 /// These API functions are intended for internal usage in generated code.
 /// Normally, you shouldn't use them.
@@ -102,7 +203,37 @@ pub mod internal {
     pub use marine_rs_sdk_main::set_result_ptr;
     pub use marine_rs_sdk_main::set_result_size;
     pub use marine_rs_sdk_main::add_object_to_release;
+    pub use crate::export_checks::check_allocations;
+    pub use crate::export_checks::check_caller;
+    pub use crate::export_checks::check_tetraplets;
+    pub use crate::export_checks::CallerPolicy;
+    pub use crate::export_checks::TetrapletRequirement;
     pub use marine_timestamp_macro::build_timestamp;
+
+    /// Called before arguments of an export function are converted from Wasm types.
+    pub fn enter_export(export_name: &'static str) {
+        marine_rs_sdk_main::enter_export(export_name);
+        marine_call_parameters::reset_call_parameters_cache();
+    }
+
+    /// Called after the original export function has returned.
+    pub fn exit_export() {
+        marine_rs_sdk_main::exit_export();
+        marine_call_parameters::reset_call_parameters_cache();
+    }
+
+    /// Reports an export error that couldn't be converted into a return value.
+    pub fn report_export_error(export_name: &str, error: &crate::ExportError) {
+        log::error!("export function {} failed: {}", export_name, error);
+    }
+
+    /// Reports an export error that couldn't be converted into a return value
+    /// and aborts the call, so host gets a trap.
+    pub fn abort_export(export_name: &str, error: &crate::ExportError) -> ! {
+        report_export_error(export_name, error);
+        exit_export();
+        std::process::abort()
+    }
 }
 
 #[cfg(not(feature = "no-explicit-ctors-call"))]
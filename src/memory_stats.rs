@@ -0,0 +1,65 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Memory usage of a module, collected by the tracking allocator of the `memory-stats` feature
+//! and exported as the `__marine_memory_stats` function.
+
+#[cfg(all(target_arch = "wasm32", feature = "marine-abi"))]
+use marine_macro::marine;
+
+/// Memory usage of a module at the moment of the call.
+#[cfg_attr(all(target_arch = "wasm32", feature = "marine-abi"), marine)]
+#[derive(Clone, PartialEq, Default, Eq, Debug)]
+pub struct MemoryStats {
+    /// Bytes of currently live allocations.
+    pub live_bytes: u64,
+
+    /// Maximum of live_bytes since the start of the module.
+    pub peak_bytes: u64,
+
+    /// Count of allocations since the start of the module.
+    pub allocations: u64,
+
+    /// Count of currently live allocations.
+    pub live_allocations: u64,
+
+    /// Count of objects kept for the host until the next `release_objects` call.
+    pub objects_to_release: u64,
+
+    /// Size of the Wasm memory in 64 KiB pages.
+    pub memory_pages: u32,
+}
+
+/// Returns memory usage of the module.
+#[cfg(all(target_arch = "wasm32", feature = "marine-abi"))]
+pub fn memory_stats() -> MemoryStats {
+    let allocation_stats = marine_rs_sdk_main::allocation_stats();
+
+    MemoryStats {
+        live_bytes: allocation_stats.live_bytes as _,
+        peak_bytes: allocation_stats.peak_bytes as _,
+        allocations: allocation_stats.allocations as _,
+        live_allocations: allocation_stats.live_allocations as _,
+        objects_to_release: marine_rs_sdk_main::objects_to_release_count() as _,
+        memory_pages: core::arch::wasm32::memory_size::<0>() as _,
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "marine-abi"))]
+#[marine]
+pub fn __marine_memory_stats() -> MemoryStats {
+    memory_stats()
+}
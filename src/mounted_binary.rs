@@ -14,6 +14,37 @@
  * limitations under the License.
  */
 
+mod command;
+mod conversions;
+mod error;
+mod status;
+#[cfg(feature = "mounted-binary-stream")]
+mod stream;
+
+pub use command::MountedBinaryCommand;
+pub use conversions::NonUtf8Output;
+pub use error::MountedBinaryError;
+pub use status::HostErrorKind;
+pub use status::MountedBinaryStatus;
+pub use status::HOST_ERROR_CODE;
+pub use status::INVALID_ARGUMENT_CODE;
+pub use status::NOT_FOUND_CODE;
+pub use status::OUTPUT_LIMIT_EXCEEDED_CODE;
+pub use status::PERMISSION_DENIED_CODE;
+pub use status::SIGNAL_CODE_BASE;
+pub use status::SPAWN_FAILED_CODE;
+pub use status::TIMEOUT_CODE;
+#[cfg(feature = "mounted-binary-stream")]
+pub use stream::MountedBinaryProcess;
+#[cfg(feature = "mounted-binary-stream")]
+pub use stream::MountedBinaryReader;
+#[cfg(feature = "mounted-binary-stream")]
+pub use stream::OutputLimits;
+#[cfg(feature = "mounted-binary-stream")]
+pub use stream::OutputStream;
+#[cfg(feature = "mounted-binary-stream")]
+pub use stream::DEFAULT_OUTPUT_LIMIT;
+
 #[cfg(all(target_arch = "wasm32", feature = "marine-abi"))]
 use marine_macro::marine;
 
@@ -26,7 +57,8 @@ pub const SUCCESS_CODE: i32 = 0;
 #[cfg_attr(all(target_arch = "wasm32", feature = "marine-abi"), marine)]
 #[derive(Clone, PartialEq, Default, Eq, Debug, Serialize, Deserialize)]
 pub struct MountedBinaryResult {
-    /// Return process exit code or host execution error code, where SUCCESS_CODE means success.
+    /// Return process exit code or host execution error code, where SUCCESS_CODE means success,
+    /// see [`MountedBinaryStatus`] for the code ranges.
     pub ret_code: i32,
 
     /// Contains the string representation of an error, if ret_code != SUCCESS_CODE.
@@ -43,7 +75,8 @@ pub struct MountedBinaryResult {
 #[cfg_attr(all(target_arch = "wasm32", feature = "marine-abi"), marine)]
 #[derive(Clone, PartialEq, Default, Eq, Debug, Serialize, Deserialize)]
 pub struct MountedBinaryStringResult {
-    /// Return process exit code or host execution error code, where SUCCESS_CODE means success.
+    /// Return process exit code or host execution error code, where SUCCESS_CODE means success,
+    /// see [`MountedBinaryStatus`] for the code ranges.
     pub ret_code: i32,
 
     /// Contains the string representation of an error, if ret_code != SUCCESS_CODE.
@@ -76,6 +109,7 @@ impl MountedBinaryResult {
     /// Internally, It checks ret_code and returns either Some(Ok(stdout)) if it was SUCCESS_CODE
     /// or Some(Err(error)) otherwise. None is returned if stdout or stderr contains non valid
     /// UTF8 string.
+    #[deprecated(since = "0.15.0", note = "please use `into_result` instead")]
     pub fn into_std(self) -> Option<std::result::Result<String, String>> {
         if self.ret_code == SUCCESS_CODE {
             let stdout = String::from_utf8(self.stdout).ok()?;
@@ -90,6 +124,7 @@ impl MountedBinaryResult {
     /// Internally, It checks ret_code and returns either Some(Ok(stdout)) if it was SUCCESS_CODE
     /// or Some(Err(error)) otherwise. None is returned if stdout or stderr contains non valid
     /// UTF8 string.
+    #[deprecated(since = "0.15.0", note = "please use `into_result` on a clone instead")]
     pub fn as_std(&self) -> Option<std::result::Result<String, String>> {
         if self.ret_code == SUCCESS_CODE {
             let stdout = String::from_utf8(self.stdout.clone()).ok()?;
@@ -100,6 +135,9 @@ impl MountedBinaryResult {
         }
     }
 
+    /// Converts stdout and stderr to strings, None is returned if any of them isn't valid UTF-8.
+    /// See [`stringify_lossy`](Self::stringify_lossy) and [`try_stringify`](Self::try_stringify)
+    /// to keep the output in this case.
     pub fn stringify(&self) -> Option<MountedBinaryStringResult> {
         let stdout = String::from_utf8(self.stdout.clone()).ok()?;
         let stderr = String::from_utf8(self.stderr.clone()).ok()?;
@@ -0,0 +1,163 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::MountedBinaryError;
+use super::MountedBinaryResult;
+use super::SUCCESS_CODE;
+
+/// Builds arguments of a mounted binary call and checks its return code.
+///
+/// ```ignore
+/// #[marine]
+/// #[link(wasm_import_module = "host")]
+/// extern "C" {
+///     pub fn curl(cmd: Vec<String>) -> MountedBinaryResult;
+/// }
+///
+/// let page = MountedBinaryCommand::new()
+///     .flag("-s")
+///     .option("-H", "Accept: text/html")
+///     .arg(url)
+///     .run(curl)?;
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MountedBinaryCommand {
+    args: Vec<String>,
+    expected_codes: Vec<i32>,
+}
+
+impl MountedBinaryCommand {
+    /// Creates a command without arguments that expects SUCCESS_CODE.
+    pub fn new() -> Self {
+        Self {
+            args: Vec::new(),
+            expected_codes: vec![SUCCESS_CODE],
+        }
+    }
+
+    /// Appends a single argument.
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Appends several arguments.
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Appends a flag without a value, e.g. `-s` or `--verbose`.
+    pub fn flag(self, name: impl Into<String>) -> Self {
+        self.arg(name)
+    }
+
+    /// Appends a flag followed by its value as a separate argument, e.g. `-H "Accept: */*"`.
+    pub fn option(self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.arg(name).arg(value)
+    }
+
+    /// Appends an env-style `KEY=value` argument.
+    pub fn env(self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        let arg = format!("{}={}", key.as_ref(), value.as_ref());
+        self.arg(arg)
+    }
+
+    /// Sets return codes treated as success, SUCCESS_CODE by default.
+    pub fn expect_codes(mut self, codes: impl IntoIterator<Item = i32>) -> Self {
+        self.expected_codes = codes.into_iter().collect();
+        self
+    }
+
+    /// Returns arguments collected so far.
+    pub fn get_args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Returns arguments, consuming the command.
+    pub fn into_args(self) -> Vec<String> {
+        self.args
+    }
+
+    /// Calls the binary import with the collected arguments and returns its stdout
+    /// if the return code is one of the expected ones.
+    pub fn run<F>(self, binary: F) -> Result<Vec<u8>, MountedBinaryError>
+    where
+        F: FnOnce(Vec<String>) -> MountedBinaryResult,
+    {
+        let expected_codes = self.expected_codes;
+        binary(self.args).into_result_with_codes(&expected_codes)
+    }
+
+    /// The same as [`run`](Self::run), but returns stdout decoded lossily as UTF-8.
+    pub fn run_string<F>(self, binary: F) -> Result<String, MountedBinaryError>
+    where
+        F: FnOnce(Vec<String>) -> MountedBinaryResult,
+    {
+        self.run(binary)
+            .map(|stdout| String::from_utf8_lossy(&stdout).into_owned())
+    }
+}
+
+impl Default for MountedBinaryCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MountedBinaryCommand;
+    use super::MountedBinaryResult;
+
+    #[test]
+    fn builds_args_in_order() {
+        let command = MountedBinaryCommand::new()
+            .flag("-s")
+            .option("-H", "Accept: */*")
+            .env("LANG", "C")
+            .args(["a", "b"])
+            .arg("url");
+
+        assert_eq!(
+            command.get_args(),
+            ["-s", "-H", "Accept: */*", "LANG=C", "a", "b", "url"]
+        );
+    }
+
+    #[test]
+    fn run_checks_expected_codes() {
+        let grep = |args: Vec<String>| MountedBinaryResult {
+            ret_code: 1,
+            error: String::new(),
+            stdout: args.join(" ").into_bytes(),
+            stderr: Vec::new(),
+        };
+
+        let stdout = MountedBinaryCommand::new()
+            .args(["-e", "x"])
+            .expect_codes([0, 1])
+            .run_string(grep);
+        assert_eq!(stdout, Ok(String::from("-e x")));
+
+        let error = MountedBinaryCommand::new().run(grep).unwrap_err();
+        assert_eq!(error.ret_code, 1);
+    }
+}
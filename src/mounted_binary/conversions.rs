@@ -0,0 +1,174 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::MountedBinaryResult;
+use super::MountedBinaryStringResult;
+
+use std::fmt;
+use std::str::Utf8Error;
+
+/// Output of a mounted binary that isn't valid UTF-8, contains the original result
+/// so its raw bytes are not lost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonUtf8Output {
+    /// The result that failed to be converted.
+    pub result: MountedBinaryResult,
+
+    /// The first error in stdout or, if stdout is valid, in stderr.
+    pub utf8_error: Utf8Error,
+}
+
+impl MountedBinaryResult {
+    /// Converts stdout and stderr to strings, replacing invalid UTF-8 sequences with U+FFFD.
+    pub fn stringify_lossy(&self) -> MountedBinaryStringResult {
+        self.stringify_with(|bytes| {
+            Ok::<_, std::convert::Infallible>(String::from_utf8_lossy(bytes).into_owned())
+        })
+        .unwrap_or_else(|never| match never {})
+    }
+
+    /// Converts stdout and stderr to strings with the provided decoder,
+    /// e.g. for binaries that print in a non UTF-8 encoding.
+    pub fn stringify_with<D, E>(&self, decoder: D) -> Result<MountedBinaryStringResult, E>
+    where
+        D: Fn(&[u8]) -> Result<String, E>,
+    {
+        let string_result = MountedBinaryStringResult {
+            ret_code: self.ret_code,
+            error: self.error.clone(),
+            stdout: decoder(&self.stdout)?,
+            stderr: decoder(&self.stderr)?,
+        };
+
+        Ok(string_result)
+    }
+
+    /// Converts stdout and stderr to strings, returning the result untouched
+    /// if any of them isn't valid UTF-8.
+    pub fn try_stringify(self) -> Result<MountedBinaryStringResult, NonUtf8Output> {
+        let utf8_error = std::str::from_utf8(&self.stdout)
+            .and_then(|_| std::str::from_utf8(&self.stderr))
+            .err();
+        if let Some(utf8_error) = utf8_error {
+            return Err(NonUtf8Output {
+                result: self,
+                utf8_error,
+            });
+        }
+
+        // both streams were checked above
+        let string_result = MountedBinaryStringResult {
+            ret_code: self.ret_code,
+            error: self.error,
+            stdout: String::from_utf8(self.stdout).unwrap(),
+            stderr: String::from_utf8(self.stderr).unwrap(),
+        };
+
+        Ok(string_result)
+    }
+
+    /// Deserializes stdout of a binary that prints JSON.
+    #[cfg(feature = "json")]
+    pub fn stdout_json<T: serde::de::DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_slice(&self.stdout)
+    }
+}
+
+impl MountedBinaryStringResult {
+    /// Deserializes stdout of a binary that prints JSON.
+    #[cfg(feature = "json")]
+    pub fn stdout_json<T: serde::de::DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_str(&self.stdout)
+    }
+}
+
+impl fmt::Display for NonUtf8Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "mounted binary output isn't valid UTF-8: {}",
+            self.utf8_error
+        )
+    }
+}
+
+impl std::error::Error for NonUtf8Output {}
+
+#[cfg(test)]
+mod tests {
+    use super::MountedBinaryResult;
+
+    fn non_utf8_result() -> MountedBinaryResult {
+        MountedBinaryResult {
+            ret_code: 0,
+            error: String::new(),
+            stdout: vec![b'o', b'k', 0xff],
+            stderr: b"warning".to_vec(),
+        }
+    }
+
+    #[test]
+    fn lossy_conversion_keeps_result() {
+        let string_result = non_utf8_result().stringify_lossy();
+        assert_eq!(string_result.stdout, "ok\u{fffd}");
+        assert_eq!(string_result.stderr, "warning");
+    }
+
+    #[test]
+    fn custom_decoder() {
+        // latin-1 maps every byte to the char with the same code
+        let latin1 = |bytes: &[u8]| Ok::<_, ()>(bytes.iter().map(|&b| b as char).collect());
+
+        let string_result = non_utf8_result().stringify_with(latin1).unwrap();
+        assert_eq!(string_result.stdout, "ok\u{ff}");
+    }
+
+    #[test]
+    fn raw_bytes_are_returned_in_error() {
+        let error = non_utf8_result().try_stringify().unwrap_err();
+        assert_eq!(error.result, non_utf8_result());
+        assert_eq!(error.utf8_error.valid_up_to(), 2);
+
+        let mut valid = non_utf8_result();
+        valid.stdout.pop();
+        assert_eq!(valid.try_stringify().unwrap().stdout, "ok");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn stdout_json() {
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct Output {
+            name: String,
+            size: u64,
+        }
+
+        let result = MountedBinaryResult {
+            stdout: br#"{"name": "file", "size": 42}"#.to_vec(),
+            ..MountedBinaryResult::default()
+        };
+
+        let expected = Output {
+            name: String::from("file"),
+            size: 42,
+        };
+        assert_eq!(result.stdout_json::<Output>().unwrap(), expected);
+        assert_eq!(
+            result.stringify_lossy().stdout_json::<Output>().unwrap(),
+            expected
+        );
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::MountedBinaryResult;
+use super::MountedBinaryStringResult;
+use super::SUCCESS_CODE;
+
+use std::fmt;
+
+/// A failed call of a mounted binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountedBinaryError {
+    /// Process exit code or host execution error code.
+    pub ret_code: i32,
+
+    /// Error reported by the host, empty if the process itself exited with a failure code.
+    pub error: String,
+
+    /// The data that the process wrote to stderr, invalid UTF-8 sequences are replaced
+    /// with U+FFFD.
+    pub stderr: String,
+}
+
+impl MountedBinaryError {
+    /// Returns true, if the binary wasn't run or didn't finish because of a host error.
+    pub fn is_host_error(&self) -> bool {
+        !self.error.is_empty()
+    }
+}
+
+impl fmt::Display for MountedBinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mounted binary failed with code {}", self.ret_code)?;
+        if !self.error.is_empty() {
+            write!(f, ", error: {}", self.error)?;
+        }
+        if !self.stderr.is_empty() {
+            write!(f, ", stderr: {}", self.stderr)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for MountedBinaryError {}
+
+impl MountedBinaryResult {
+    /// Returns stdout if ret_code is SUCCESS_CODE, otherwise a [`MountedBinaryError`].
+    pub fn into_result(self) -> Result<Vec<u8>, MountedBinaryError> {
+        self.into_result_with_codes(&[SUCCESS_CODE])
+    }
+
+    /// Returns stdout if ret_code is one of the expected codes, otherwise a [`MountedBinaryError`].
+    pub fn into_result_with_codes(
+        self,
+        expected_codes: &[i32],
+    ) -> Result<Vec<u8>, MountedBinaryError> {
+        if expected_codes.contains(&self.ret_code) {
+            return Ok(self.stdout);
+        }
+
+        Err(MountedBinaryError {
+            ret_code: self.ret_code,
+            error: self.error,
+            stderr: String::from_utf8_lossy(&self.stderr).into_owned(),
+        })
+    }
+}
+
+impl MountedBinaryStringResult {
+    /// Returns stdout if ret_code is SUCCESS_CODE, otherwise a [`MountedBinaryError`].
+    pub fn into_result(self) -> Result<String, MountedBinaryError> {
+        self.into_result_with_codes(&[SUCCESS_CODE])
+    }
+
+    /// Returns stdout if ret_code is one of the expected codes, otherwise a [`MountedBinaryError`].
+    pub fn into_result_with_codes(
+        self,
+        expected_codes: &[i32],
+    ) -> Result<String, MountedBinaryError> {
+        if expected_codes.contains(&self.ret_code) {
+            return Ok(self.stdout);
+        }
+
+        Err(MountedBinaryError {
+            ret_code: self.ret_code,
+            error: self.error,
+            stderr: self.stderr,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MountedBinaryResult;
+
+    #[test]
+    fn failed_result_keeps_code_and_lossy_stderr() {
+        let result = MountedBinaryResult {
+            ret_code: 2,
+            error: String::new(),
+            stdout: b"partial".to_vec(),
+            stderr: vec![b'b', b'a', b'd', 0xff],
+        };
+
+        let error = result.into_result().unwrap_err();
+        assert_eq!(error.ret_code, 2);
+        assert_eq!(error.stderr, "bad\u{fffd}");
+        assert!(!error.is_host_error());
+        assert_eq!(
+            error.to_string(),
+            "mounted binary failed with code 2, stderr: bad\u{fffd}"
+        );
+    }
+
+    #[test]
+    fn expected_codes_are_success() {
+        let result = MountedBinaryResult {
+            ret_code: 1,
+            error: String::new(),
+            stdout: b"no matches".to_vec(),
+            stderr: Vec::new(),
+        };
+
+        assert_eq!(
+            result.clone().into_result_with_codes(&[0, 1]),
+            Ok(b"no matches".to_vec())
+        );
+        assert!(result.into_result().is_err());
+    }
+}
@@ -0,0 +1,279 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Classification of `ret_code` of mounted binaries results.
+//!
+//! Codes `0..=255` are exit codes of a process, codes from `SIGNAL_CODE_BASE` up to
+//! `SIGNAL_CODE_BASE + 255` mean that a process was terminated by a signal, and negative
+//! codes are reported by host when a binary couldn't be run or finished.
+//!
+//! Negative codes below are the contract of the mounted binary functions of the host API v4,
+//! see the `marine-host-api` crate. Hosts of the v3 don't use them and report a failure to run
+//! a binary only by a non-empty `error`, so results and errors with a non-negative code and
+//! a non-empty `error` are classified as [`HostErrorKind::Other`].
+//! `EXPORT_ERROR_CODE` is set by the SDK itself for results of failed export functions.
+
+use super::MountedBinaryError;
+use super::MountedBinaryResult;
+use super::MountedBinaryStringResult;
+use crate::EXPORT_ERROR_CODE;
+
+/// Process was terminated by the signal `ret_code - SIGNAL_CODE_BASE`.
+pub const SIGNAL_CODE_BASE: i32 = 256;
+
+/// Host failed for a reason not covered by other codes.
+pub const HOST_ERROR_CODE: i32 = -1;
+
+/// Process didn't finish in time and was killed by host.
+pub const TIMEOUT_CODE: i32 = -2;
+
+/// Binary isn't mounted to the module.
+pub const NOT_FOUND_CODE: i32 = -3;
+
+/// Host isn't allowed to run the binary.
+pub const PERMISSION_DENIED_CODE: i32 = -4;
+
+/// Host failed to spawn a process, e.g. because of the process limit.
+pub const SPAWN_FAILED_CODE: i32 = -5;
+
+/// Process output exceeded the limit set by host.
+pub const OUTPUT_LIMIT_EXCEEDED_CODE: i32 = -6;
+
+/// Arguments can't be passed to the binary.
+pub const INVALID_ARGUMENT_CODE: i32 = -7;
+
+/// Outcome of a mounted binary call derived from its `ret_code`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MountedBinaryStatus {
+    /// Process exited with the code, 0 means success.
+    Exited(u8),
+
+    /// Process was terminated by the signal.
+    Signaled(u8),
+
+    /// Host failed to run the binary or to get its result.
+    HostError(HostErrorKind),
+
+    /// Process was killed by host after a timeout.
+    Timeout,
+
+    /// Result was created by a failed export function, see [`crate::ExportError`].
+    ExportError,
+}
+
+/// Kind of a host error, see `*_CODE` constants.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HostErrorKind {
+    Other,
+    NotFound,
+    PermissionDenied,
+    SpawnFailed,
+    OutputLimitExceeded,
+    InvalidArgument,
+    /// A code outside of the known ranges.
+    Unknown(i32),
+}
+
+impl MountedBinaryStatus {
+    pub fn from_code(ret_code: i32) -> Self {
+        const SIGNAL_CODE_END: i32 = SIGNAL_CODE_BASE + u8::MAX as i32;
+
+        match ret_code {
+            0..=255 => Self::Exited(ret_code as u8),
+            SIGNAL_CODE_BASE..=SIGNAL_CODE_END => {
+                Self::Signaled((ret_code - SIGNAL_CODE_BASE) as u8)
+            }
+            TIMEOUT_CODE => Self::Timeout,
+            EXPORT_ERROR_CODE => Self::ExportError,
+            ret_code => Self::HostError(HostErrorKind::from_code(ret_code)),
+        }
+    }
+
+    /// Classifies ret_code taking into account errors reported by hosts of the v3,
+    /// which don't use negative codes.
+    pub fn from_code_and_error(ret_code: i32, error: &str) -> Self {
+        match Self::from_code(ret_code) {
+            Self::Exited(_) | Self::Signaled(_) if !error.is_empty() => {
+                Self::HostError(HostErrorKind::Other)
+            }
+            status => status,
+        }
+    }
+
+    /// Returns true, if process exited with SUCCESS_CODE.
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Exited(0))
+    }
+
+    /// Returns true, if the call could succeed when repeated with the same arguments,
+    /// i.e. it was interrupted by a timeout or host failed to spawn a process,
+    /// e.g. because of the process limit, rather than failed because of its input.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::Timeout | Self::HostError(HostErrorKind::SpawnFailed)
+        )
+    }
+}
+
+impl HostErrorKind {
+    pub fn from_code(ret_code: i32) -> Self {
+        match ret_code {
+            HOST_ERROR_CODE => Self::Other,
+            NOT_FOUND_CODE => Self::NotFound,
+            PERMISSION_DENIED_CODE => Self::PermissionDenied,
+            SPAWN_FAILED_CODE => Self::SpawnFailed,
+            OUTPUT_LIMIT_EXCEEDED_CODE => Self::OutputLimitExceeded,
+            INVALID_ARGUMENT_CODE => Self::InvalidArgument,
+            ret_code => Self::Unknown(ret_code),
+        }
+    }
+
+    pub fn code(&self) -> i32 {
+        match self {
+            Self::Other => HOST_ERROR_CODE,
+            Self::NotFound => NOT_FOUND_CODE,
+            Self::PermissionDenied => PERMISSION_DENIED_CODE,
+            Self::SpawnFailed => SPAWN_FAILED_CODE,
+            Self::OutputLimitExceeded => OUTPUT_LIMIT_EXCEEDED_CODE,
+            Self::InvalidArgument => INVALID_ARGUMENT_CODE,
+            Self::Unknown(ret_code) => *ret_code,
+        }
+    }
+}
+
+impl MountedBinaryResult {
+    /// Classifies ret_code of this result.
+    pub fn status(&self) -> MountedBinaryStatus {
+        MountedBinaryStatus::from_code_and_error(self.ret_code, &self.error)
+    }
+
+    /// Returns true, if the process was killed by host after a timeout.
+    pub fn is_timeout(&self) -> bool {
+        self.status() == MountedBinaryStatus::Timeout
+    }
+
+    /// See [`MountedBinaryStatus::is_retryable`].
+    pub fn is_retryable(&self) -> bool {
+        self.status().is_retryable()
+    }
+}
+
+impl MountedBinaryStringResult {
+    /// Classifies ret_code of this result.
+    pub fn status(&self) -> MountedBinaryStatus {
+        MountedBinaryStatus::from_code_and_error(self.ret_code, &self.error)
+    }
+
+    /// Return true, if this Result represents a success result, otherwise false.
+    pub fn is_success(&self) -> bool {
+        self.ret_code == super::SUCCESS_CODE
+    }
+
+    /// Returns true, if the process was killed by host after a timeout.
+    pub fn is_timeout(&self) -> bool {
+        self.status() == MountedBinaryStatus::Timeout
+    }
+
+    /// See [`MountedBinaryStatus::is_retryable`].
+    pub fn is_retryable(&self) -> bool {
+        self.status().is_retryable()
+    }
+}
+
+impl MountedBinaryError {
+    /// Classifies ret_code of this error.
+    pub fn status(&self) -> MountedBinaryStatus {
+        MountedBinaryStatus::from_code_and_error(self.ret_code, &self.error)
+    }
+
+    /// See [`MountedBinaryStatus::is_retryable`].
+    pub fn is_retryable(&self) -> bool {
+        self.status().is_retryable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HostErrorKind;
+    use super::MountedBinaryStatus;
+    use super::MountedBinaryResult;
+    use super::EXPORT_ERROR_CODE;
+    use super::HOST_ERROR_CODE;
+    use super::INVALID_ARGUMENT_CODE;
+    use super::NOT_FOUND_CODE;
+    use super::TIMEOUT_CODE;
+
+    #[test]
+    fn codes_are_classified_by_ranges() {
+        use MountedBinaryStatus::*;
+
+        assert_eq!(MountedBinaryStatus::from_code(0), Exited(0));
+        assert_eq!(MountedBinaryStatus::from_code(255), Exited(255));
+        assert_eq!(MountedBinaryStatus::from_code(256 + 9), Signaled(9));
+        assert_eq!(MountedBinaryStatus::from_code(TIMEOUT_CODE), Timeout);
+        assert_eq!(
+            MountedBinaryStatus::from_code(NOT_FOUND_CODE),
+            HostError(HostErrorKind::NotFound)
+        );
+        assert_eq!(
+            MountedBinaryStatus::from_code(1024),
+            HostError(HostErrorKind::Unknown(1024))
+        );
+        assert_eq!(
+            MountedBinaryStatus::from_code(HOST_ERROR_CODE),
+            HostError(HostErrorKind::Other)
+        );
+        assert_eq!(
+            MountedBinaryStatus::from_code(EXPORT_ERROR_CODE),
+            ExportError
+        );
+    }
+
+    #[test]
+    fn errors_of_v3_hosts_are_host_errors() {
+        let host_error = MountedBinaryResult::from_error(1, "binary isn't mounted");
+        assert_eq!(
+            host_error.status(),
+            MountedBinaryStatus::HostError(HostErrorKind::Other)
+        );
+
+        let exit_error = MountedBinaryResult::from_error(1, "");
+        assert_eq!(exit_error.status(), MountedBinaryStatus::Exited(1));
+    }
+
+    #[test]
+    fn host_error_codes_roundtrip() {
+        for code in -8..0 {
+            if code == TIMEOUT_CODE {
+                continue;
+            }
+
+            assert_eq!(HostErrorKind::from_code(code).code(), code);
+        }
+    }
+
+    #[test]
+    fn only_transient_failures_are_retryable() {
+        let timeout = MountedBinaryResult::from_error(TIMEOUT_CODE, "timeout");
+        assert!(timeout.is_timeout());
+        assert!(timeout.is_retryable());
+
+        let user_error = MountedBinaryResult::from_error(2, "");
+        assert!(!user_error.is_retryable());
+        assert!(!MountedBinaryStatus::from_code(INVALID_ARGUMENT_CODE).is_retryable());
+    }
+}
@@ -0,0 +1,492 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A handle-based protocol for reading output of mounted binaries in chunks.
+//!
+//! A binary is spawned by the host, which keeps its output and stops collecting it
+//! after the configured limits, so a module could read large outputs piece by piece
+//! instead of receiving them as a single `MountedBinaryResult`.
+//!
+//! The `mounted_binary_*` host functions are provided only by the host API v4,
+//! see the `marine-host-api` crate for their contract.
+
+use super::MountedBinaryCommand;
+use super::MountedBinaryError;
+use super::MountedBinaryResult;
+use super::HOST_ERROR_CODE;
+use super::INVALID_ARGUMENT_CODE;
+use super::OUTPUT_LIMIT_EXCEEDED_CODE;
+
+use std::io;
+
+/// Default limit of stdout and stderr kept by the host for a spawned binary, 16 MiB.
+pub const DEFAULT_OUTPUT_LIMIT: u64 = 16 * 1024 * 1024;
+
+/// Size of chunks requested from the host by readers of a spawned binary.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+const OUTPUT_LIMIT_EXCEEDED_CODE_I64: i64 = OUTPUT_LIMIT_EXCEEDED_CODE as i64;
+
+/// Limits of the output the host keeps for a spawned binary, in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutputLimits {
+    pub stdout: u64,
+    pub stderr: u64,
+}
+
+impl Default for OutputLimits {
+    fn default() -> Self {
+        Self {
+            stdout: DEFAULT_OUTPUT_LIMIT,
+            stderr: DEFAULT_OUTPUT_LIMIT,
+        }
+    }
+}
+
+/// Output stream of a spawned binary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout = 0,
+    Stderr = 1,
+}
+
+/// A mounted binary spawned by the host. The handle is released when the process is dropped.
+///
+/// ```ignore
+/// let mut process = MountedBinaryCommand::new()
+///     .flag("-s")
+///     .arg(url)
+///     .spawn("curl", OutputLimits::default())?;
+///
+/// let mut chunk = vec![0; 4096];
+/// loop {
+///     let size = process.read(OutputStream::Stdout, &mut chunk)?;
+///     if size == 0 {
+///         break;
+///     }
+///     consume(&chunk[..size]);
+/// }
+///
+/// if process.is_truncated(OutputStream::Stdout) {
+///     log::warn!("curl output exceeds the limit");
+/// }
+/// let result = process.wait();
+/// ```
+#[derive(Debug)]
+pub struct MountedBinaryProcess {
+    handle: i64,
+    truncated: [bool; 2],
+}
+
+/// Reads one output stream of a spawned binary, see [`MountedBinaryProcess::reader`].
+#[derive(Debug)]
+pub struct MountedBinaryReader<'p> {
+    process: &'p mut MountedBinaryProcess,
+    stream: OutputStream,
+}
+
+impl MountedBinaryProcess {
+    /// Spawns the mounted binary with the provided arguments.
+    pub fn spawn(
+        binary: &str,
+        args: &[String],
+        limits: OutputLimits,
+    ) -> Result<Self, MountedBinaryError> {
+        let args = encode_args(args)?;
+        let handle = host::spawn(binary, &args, limits);
+        if handle < 0 {
+            return Err(host_error(handle));
+        }
+
+        Ok(Self {
+            handle,
+            truncated: [false; 2],
+        })
+    }
+
+    /// Reads the next chunk of the output stream into the buffer.
+    /// Returns 0 at the end of the stream, use [`Self::is_truncated`] to check
+    /// whether the stream was truncated by its limit.
+    pub fn read(
+        &mut self,
+        stream: OutputStream,
+        buf: &mut [u8],
+    ) -> Result<usize, MountedBinaryError> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let size = host::read(self.handle, stream, buf);
+        self.handle_read(stream, size)
+    }
+
+    /// Converts a value returned by the read host function to the read size.
+    fn handle_read(
+        &mut self,
+        stream: OutputStream,
+        size: i64,
+    ) -> Result<usize, MountedBinaryError> {
+        match size {
+            OUTPUT_LIMIT_EXCEEDED_CODE_I64 => {
+                self.truncated[stream as usize] = true;
+                Ok(0)
+            }
+            size if size < 0 => Err(host_error(size)),
+            size => Ok(size as usize),
+        }
+    }
+
+    /// Returns true if the end of the output stream was read and the host had dropped
+    /// the output after the stream limit.
+    pub fn is_truncated(&self, stream: OutputStream) -> bool {
+        self.truncated[stream as usize]
+    }
+
+    /// Returns an `io::Read` implementation for the output stream.
+    pub fn reader(&mut self, stream: OutputStream) -> MountedBinaryReader<'_> {
+        MountedBinaryReader {
+            process: self,
+            stream,
+        }
+    }
+
+    /// Waits for the binary to finish and returns its return code and host error.
+    /// The output that wasn't read is dropped.
+    pub fn wait(self) -> MountedBinaryResult {
+        wait_result(host::wait(self.handle))
+    }
+
+    /// Reads the whole output and waits for the binary, producing the same result
+    /// as a call of the binary import. Suitable for small outputs only,
+    /// the output truncated by limits is returned as is.
+    pub fn into_mounted_binary_result(mut self) -> MountedBinaryResult {
+        let output = read_to_end(&mut self.reader(OutputStream::Stdout)).and_then(|stdout| {
+            read_to_end(&mut self.reader(OutputStream::Stderr)).map(|stderr| (stdout, stderr))
+        });
+
+        match output {
+            Ok((stdout, stderr)) => MountedBinaryResult {
+                stdout,
+                stderr,
+                ..self.wait()
+            },
+            Err(error) => MountedBinaryResult::from_error(error.ret_code, error.error),
+        }
+    }
+}
+
+impl Drop for MountedBinaryProcess {
+    fn drop(&mut self) {
+        host::close(self.handle);
+    }
+}
+
+impl MountedBinaryReader<'_> {
+    /// Returns true if the stream was read to the end and truncated by its limit.
+    pub fn is_truncated(&self) -> bool {
+        self.process.is_truncated(self.stream)
+    }
+}
+
+impl io::Read for MountedBinaryReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.process
+            .read(self.stream, buf)
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))
+    }
+}
+
+impl MountedBinaryCommand {
+    /// Spawns the mounted binary with the collected arguments to read its output in chunks.
+    /// Expected codes aren't checked, see [`MountedBinaryProcess::wait`].
+    pub fn spawn(
+        &self,
+        binary: &str,
+        limits: OutputLimits,
+    ) -> Result<MountedBinaryProcess, MountedBinaryError> {
+        MountedBinaryProcess::spawn(binary, self.get_args(), limits)
+    }
+}
+
+/// Arguments are passed to the host each terminated by '\0', which can't occur inside them,
+/// so no arguments and a single empty argument are encoded differently.
+fn encode_args(args: &[String]) -> Result<Vec<u8>, MountedBinaryError> {
+    if let Some(arg) = args.iter().find(|arg| arg.contains('\0')) {
+        return Err(MountedBinaryError {
+            ret_code: INVALID_ARGUMENT_CODE,
+            error: format!("argument {:?} contains a nul byte", arg),
+            stderr: String::new(),
+        });
+    }
+
+    let encoded = args
+        .iter()
+        .flat_map(|arg| arg.bytes().chain(std::iter::once(0)))
+        .collect();
+
+    Ok(encoded)
+}
+
+fn read_to_end(reader: &mut impl io::Read) -> Result<Vec<u8>, MountedBinaryError> {
+    let mut output = Vec::new();
+    let mut chunk = vec![0; CHUNK_SIZE];
+    loop {
+        match reader.read(&mut chunk) {
+            Ok(0) => return Ok(output),
+            Ok(size) => output.extend_from_slice(&chunk[..size]),
+            Err(error) => {
+                return Err(match error.into_inner() {
+                    Some(error) => match error.downcast::<MountedBinaryError>() {
+                        Ok(error) => *error,
+                        Err(error) => MountedBinaryError::from_io(error.to_string()),
+                    },
+                    None => MountedBinaryError::from_io(String::from("read failed")),
+                })
+            }
+        }
+    }
+}
+
+/// Host sets an error message only for negative codes, exit and signal codes have no message.
+fn wait_result(ret_code: i32) -> MountedBinaryResult {
+    let error = match ret_code {
+        ret_code if ret_code < 0 => host::take_error(),
+        _ => String::new(),
+    };
+
+    MountedBinaryResult {
+        ret_code,
+        error,
+        stdout: Vec::new(),
+        stderr: Vec::new(),
+    }
+}
+
+fn host_error(ret_code: i64) -> MountedBinaryError {
+    MountedBinaryError {
+        ret_code: ret_code as i32,
+        error: host::take_error(),
+        stderr: String::new(),
+    }
+}
+
+impl MountedBinaryError {
+    fn from_io(error: String) -> Self {
+        Self {
+            ret_code: HOST_ERROR_CODE,
+            error,
+            stderr: String::new(),
+        }
+    }
+}
+
+#[cfg(all(feature = "marine-abi", target_arch = "wasm32"))]
+mod host {
+    use super::OutputLimits;
+    use super::OutputStream;
+
+    // it's safe until it is executed on standard Fluence node with appropriate import functions
+    pub(super) fn spawn(binary: &str, args: &[u8], limits: OutputLimits) -> i64 {
+        unsafe {
+            mounted_binary_spawn(
+                binary.as_ptr() as _,
+                binary.len() as _,
+                args.as_ptr() as _,
+                args.len() as _,
+                limits.stdout,
+                limits.stderr,
+            )
+        }
+    }
+
+    pub(super) fn read(handle: i64, stream: OutputStream, buf: &mut [u8]) -> i64 {
+        unsafe { mounted_binary_read(handle, stream as _, buf.as_mut_ptr() as _, buf.len() as _) }
+    }
+
+    pub(super) fn wait(handle: i64) -> i32 {
+        unsafe { mounted_binary_wait(handle) }
+    }
+
+    pub(super) fn close(handle: i64) {
+        unsafe { mounted_binary_close(handle) }
+    }
+
+    /// Takes an error message the host has set as a result of the last failed call.
+    /// The result globals are cleared, so the message can't be taken twice.
+    pub(super) fn take_error() -> String {
+        unsafe {
+            let ptr = marine_rs_sdk_main::get_result_ptr();
+            let size = marine_rs_sdk_main::get_result_size();
+            marine_rs_sdk_main::set_result_ptr(0);
+            marine_rs_sdk_main::set_result_size(0);
+            match size {
+                0 => String::new(),
+                _ => String::from_raw_parts(ptr as _, size as _, size as _),
+            }
+        }
+    }
+
+    marine_rs_sdk_main::host_api_imports! {
+        extern "C" {
+            // spawns a binary with '\0'-terminated arguments, returns a handle or a negative error code
+            fn mounted_binary_spawn(
+                binary_ptr: u32,
+                binary_size: u32,
+                args_ptr: u32,
+                args_size: u32,
+                stdout_limit: u64,
+                stderr_limit: u64,
+            ) -> i64;
+
+            // copies the next chunk of a stream into the buffer, returns its size,
+            // 0 at the end of the stream, OUTPUT_LIMIT_EXCEEDED_CODE at the end of a stream
+            // truncated by its limit or another negative error code
+            fn mounted_binary_read(handle: i64, stream: u32, buf_ptr: u32, buf_size: u32) -> i64;
+
+            // waits for the binary and returns its return code
+            fn mounted_binary_wait(handle: i64) -> i32;
+
+            // releases the handle and the output kept by host
+            fn mounted_binary_close(handle: i64);
+        }
+    }
+}
+
+// mounted binaries are spawned only by host, so natively every call fails
+#[cfg(not(all(feature = "marine-abi", target_arch = "wasm32")))]
+mod host {
+    use super::OutputLimits;
+    use super::OutputStream;
+    use super::HOST_ERROR_CODE;
+
+    pub(super) fn spawn(_binary: &str, _args: &[u8], _limits: OutputLimits) -> i64 {
+        HOST_ERROR_CODE as _
+    }
+
+    pub(super) fn read(_handle: i64, _stream: OutputStream, _buf: &mut [u8]) -> i64 {
+        HOST_ERROR_CODE as _
+    }
+
+    pub(super) fn wait(_handle: i64) -> i32 {
+        HOST_ERROR_CODE
+    }
+
+    pub(super) fn close(_handle: i64) {}
+
+    pub(super) fn take_error() -> String {
+        String::from("mounted binaries can be spawned only by host in Wasm modules")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode_args;
+    use super::read_to_end;
+    use super::wait_result;
+    use super::MountedBinaryError;
+    use super::MountedBinaryProcess;
+    use super::OutputStream;
+    use super::OUTPUT_LIMIT_EXCEEDED_CODE;
+    use crate::mounted_binary::SIGNAL_CODE_BASE;
+    use crate::mounted_binary::TIMEOUT_CODE;
+    use std::io;
+
+    struct ChunkedReader {
+        chunks: Vec<Result<Vec<u8>, MountedBinaryError>>,
+    }
+
+    impl io::Read for ChunkedReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.chunks.is_empty() {
+                return Ok(0);
+            }
+
+            match self.chunks.remove(0) {
+                Ok(chunk) => {
+                    buf[..chunk.len()].copy_from_slice(&chunk);
+                    Ok(chunk.len())
+                }
+                Err(error) => Err(io::Error::new(io::ErrorKind::Other, error)),
+            }
+        }
+    }
+
+    #[test]
+    fn args_are_nul_terminated() {
+        let args = vec![String::from("-s"), String::from("url")];
+        assert_eq!(encode_args(&args).unwrap(), b"-s\0url\0");
+
+        assert_eq!(encode_args(&[]).unwrap(), b"");
+        assert_eq!(encode_args(&[String::new()]).unwrap(), b"\0");
+
+        let args = vec![String::from("a\0b")];
+        assert!(encode_args(&args).is_err());
+    }
+
+    #[test]
+    fn truncation_is_reported_at_end_of_stream() {
+        let mut process = MountedBinaryProcess {
+            handle: 0,
+            truncated: [false; 2],
+        };
+
+        assert_eq!(process.handle_read(OutputStream::Stdout, 7), Ok(7));
+        assert_eq!(process.handle_read(OutputStream::Stdout, 0), Ok(0));
+        assert!(!process.is_truncated(OutputStream::Stdout));
+
+        let end = process.handle_read(OutputStream::Stderr, OUTPUT_LIMIT_EXCEEDED_CODE as i64);
+        assert_eq!(end, Ok(0));
+        assert!(process.is_truncated(OutputStream::Stderr));
+        assert!(!process.is_truncated(OutputStream::Stdout));
+
+        let error = process.handle_read(OutputStream::Stdout, TIMEOUT_CODE as i64);
+        assert_eq!(error.unwrap_err().ret_code, TIMEOUT_CODE);
+    }
+
+    #[test]
+    fn only_host_errors_have_messages() {
+        assert_eq!(wait_result(0).error, "");
+        assert_eq!(wait_result(1).error, "");
+        assert_eq!(wait_result(SIGNAL_CODE_BASE + 9).error, "");
+
+        let timeout = wait_result(TIMEOUT_CODE);
+        assert_eq!(timeout.ret_code, TIMEOUT_CODE);
+        assert!(!timeout.error.is_empty());
+    }
+
+    #[test]
+    fn chunks_are_concatenated() {
+        let mut reader = ChunkedReader {
+            chunks: vec![Ok(b"hello ".to_vec()), Ok(b"world".to_vec())],
+        };
+
+        assert_eq!(read_to_end(&mut reader).unwrap(), b"hello world");
+    }
+
+    #[test]
+    fn host_errors_are_preserved() {
+        let error = MountedBinaryError {
+            ret_code: -3,
+            error: String::from("handle is closed"),
+            stderr: String::new(),
+        };
+        let mut reader = ChunkedReader {
+            chunks: vec![Ok(b"partial".to_vec()), Err(error.clone())],
+        };
+
+        assert_eq!(read_to_end(&mut reader), Err(error));
+    }
+}
@@ -0,0 +1,42 @@
+/*
+ * Copyright 2024 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(all(target_arch = "wasm32", feature = "marine-abi"))]
+use marine_macro::marine;
+
+/// Adjusts log levels of the module logger at runtime, so that a node operator could turn on
+/// verbose logging for a service without redeploying it. An empty target sets the default log
+/// level, otherwise a directive for this target is added or replaced. Level is one of `off`,
+/// `error`, `warn`, `info`, `debug` or `trace`.
+///
+/// Returns false if the level couldn't be parsed or a logger hasn't been built yet.
+#[cfg_attr(all(target_arch = "wasm32", feature = "marine-abi"), marine)]
+#[cfg_attr(
+    not(all(target_arch = "wasm32", feature = "marine-abi")),
+    allow(dead_code)
+)]
+pub fn __marine_set_log_level(target: String, level: String) -> bool {
+    let level = match level.parse::<log::LevelFilter>() {
+        Ok(level) => level,
+        Err(_) => return false,
+    };
+    let target = match target.as_str() {
+        "" => None,
+        target => Some(target),
+    };
+
+    marine_rs_sdk_main::set_log_level(target, level)
+}
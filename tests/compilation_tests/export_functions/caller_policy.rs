@@ -0,0 +1,16 @@
+#![allow(improper_ctypes)]
+
+use marine_rs_sdk::marine;
+use marine_rs_sdk::MountedBinaryResult;
+
+pub fn main() {}
+
+#[marine(only_owner)]
+pub fn owner_only(arg: String) -> MountedBinaryResult {
+    MountedBinaryResult::from_error(1, arg)
+}
+
+#[marine(only_host, allow_peers = ["peer_1", "peer_2"])]
+pub fn host_or_peers(#[marine(require_tetraplet(literal))] arg: String) -> MountedBinaryResult {
+    MountedBinaryResult::from_error(1, arg)
+}
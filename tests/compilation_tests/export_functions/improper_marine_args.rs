@@ -0,0 +1,27 @@
+#![allow(improper_ctypes)]
+
+use marine_rs_sdk::marine;
+
+pub fn main() {}
+
+#[marine(only_owner, unknown_arg)]
+pub fn unknown_arg(_arg: String) {}
+
+#[marine(only_owner)]
+pub struct Record {
+    pub field: i32,
+}
+
+#[marine(only_owner, only_owner)]
+pub fn duplicated_arg(_arg: String) {}
+
+#[marine(allow_peers = "peer_1")]
+pub fn peers_not_in_list(_arg: String) {}
+
+#[marine(only_owner)]
+pub fn without_result(_arg: String) {}
+
+#[marine(only_host, allow_peers = ["peer_1"])]
+pub fn ref_result(_arg: String) -> &'static String {
+    unimplemented!()
+}
@@ -0,0 +1,25 @@
+#![allow(improper_ctypes)]
+
+use marine_rs_sdk::marine;
+
+pub fn main() {}
+
+#[marine]
+pub fn unknown_requirement(#[marine(require_tetraplet(peer = "peer"))] _arg: String) {}
+
+#[marine]
+pub fn duplicated_requirement(
+    #[marine(require_tetraplet(peer_id = "peer", peer_id = "other_peer"))] _arg: String,
+) {
+}
+
+#[marine]
+pub fn unknown_argument_attribute(#[marine(catch_panic)] _arg: String) {}
+
+#[marine]
+pub fn without_result(#[marine(require_tetraplet(function_name = "get"))] _arg: Vec<u8>) {}
+
+#[marine]
+pub fn ref_result(#[marine(require_tetraplet(literal))] _arg: String) -> &'static String {
+    unimplemented!()
+}
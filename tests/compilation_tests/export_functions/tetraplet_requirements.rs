@@ -0,0 +1,14 @@
+#![allow(improper_ctypes)]
+
+use marine_rs_sdk::marine;
+use marine_rs_sdk::MountedBinaryResult;
+
+pub fn main() {}
+
+#[marine]
+pub fn set_value(
+    #[marine(require_tetraplet(peer_id = "peer", service_id = "service", no_lens))] key: String,
+    #[marine(require_tetraplet(literal))] value: u64,
+) -> MountedBinaryResult {
+    MountedBinaryResult::from_error(value as _, key)
+}
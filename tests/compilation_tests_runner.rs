@@ -8,6 +8,12 @@ fn marine_compilation_tests() {
     tests.pass("tests/compilation_tests/export_functions/basic_types.rs");
     tests.pass("tests/compilation_tests/export_functions/ref_basic_types.rs");
     tests.compile_fail("tests/compilation_tests/export_functions/improper_types.rs");
+    tests.compile_fail("tests/compilation_tests/export_functions/improper_marine_args.rs");
+    tests.pass("tests/compilation_tests/export_functions/tetraplet_requirements.rs");
+    tests.pass("tests/compilation_tests/export_functions/caller_policy.rs");
+    tests.compile_fail(
+        "tests/compilation_tests/export_functions/improper_tetraplet_requirements.rs",
+    );
 
     tests.compile_fail("tests/compilation_tests/import_functions/arrays_out_inner_refs.rs");
     tests.pass("tests/compilation_tests/import_functions/arrays.rs");